@@ -1,12 +1,16 @@
 #![deny(clippy::all)]
 
-use std::str::FromStr;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
 
-use crate::support::check_size;
+use crate::support::{hex_to_bytes, ParseError};
 
 const UMASK63: u64 = (1 << 63) - 1; // 0x7FFF_FFFF_FFFF_FFFF
 
-#[derive(PartialEq, Clone, Copy, Default)]
+// sqrt(-1) = 2**((p - 1) / 4), the 'positive' (even) root
+pub(crate) const SQRT_M1: Fe25519 = Fe25519 { x3: 0x2B83_2480_4FC1_DF0B, x2: 0x2B4D_0099_3DFB_D7A7, x1: 0x2F43_1806_AD2F_E478, x0: 0xC4EE_1B27_4A0E_A0B0 };
+
+#[derive(Clone, Copy, Default)]
 pub struct Fe25519 {
     // 63+64+64+64=255; x3 is MSB
     pub x3: u64,
@@ -15,47 +19,169 @@ pub struct Fe25519 {
     pub x0: u64,
 }
 
-pub fn get_u(src: &str) -> Fe25519 {
-    let new_u = format!("{}", src);
-    let temp1 = Fe25519::from_str(&new_u).unwrap();
-    let temp2 = Fe25519 { x3: u64::from_be(temp1.x0), x2: u64::from_be(temp1.x1), x1: u64::from_be(temp1.x2), x0: u64::from_be(temp1.x3) };
+// Fe25519 stays Copy for the arithmetic, so it cannot wipe itself on drop; holders of secret
+// values call zeroize() explicitly (see mul and fe_invert) or wrap it in a type that does
+impl Zeroize for Fe25519 {
+    fn zeroize(&mut self) {
+        self.x3.zeroize();
+        self.x2.zeroize();
+        self.x1.zeroize();
+        self.x0.zeroize();
+    }
+}
 
-    // Rollover logic driven by the case (2**255 - 19) + (small, e.g. 4)
-    // Need run-time check; point mult may never encounter this
-    let x0_roll_19 = u128::from(temp2.x0) + 19;
-    let roll_x0 = x0_roll_19 as u64;
-    let x1_roll_19 = (x0_roll_19 >> 64) + u128::from(temp2.x1);
-    let roll_x1 = x1_roll_19 as u64;
-    let x2_roll_19 = (x1_roll_19 >> 64) + u128::from(temp2.x2);
-    let roll_x2 = x2_roll_19 as u64;
-    let x3_roll_19 = (x2_roll_19 >> 64) + u128::from(temp2.x3);
-    let roll_x3 = x3_roll_19 as u64;
-    let rollover = 0u64.overflowing_sub((x3_roll_19 >> 63) as u64).0;
+impl ConstantTimeEq for Fe25519 {
+    // Compares the values mod p, so non-canonical limbs still compare equal to their reduction
+    fn ct_eq(&self, other: &Fe25519) -> Choice {
+        let lhs = fe_reduce(self);
+        let rhs = fe_reduce(other);
+        lhs.x3.ct_eq(&rhs.x3) & lhs.x2.ct_eq(&rhs.x2) & lhs.x1.ct_eq(&rhs.x1) & lhs.x0.ct_eq(&rhs.x0)
+    }
+}
 
-    // Based on rollover, choose original sum or 'incremented by 19' sum
-    Fe25519 {
-        x3: UMASK63 & (!rollover & temp2.x3 | rollover & roll_x3),
-        x2: !rollover & temp2.x2 | rollover & roll_x2,
-        x1: !rollover & temp2.x1 | rollover & roll_x1,
-        x0: !rollover & temp2.x0 | rollover & roll_x0,
+// Equality never short-circuits on the first differing limb
+impl PartialEq for Fe25519 {
+    fn eq(&self, other: &Fe25519) -> bool {
+        self.ct_eq(other).into()
     }
 }
 
+impl Eq for Fe25519 {}
+
+impl ConditionallySelectable for Fe25519 {
+    fn conditional_select(a: &Fe25519, b: &Fe25519, choice: Choice) -> Fe25519 {
+        Fe25519 {
+            x3: u64::conditional_select(&a.x3, &b.x3, choice),
+            x2: u64::conditional_select(&a.x2, &b.x2, choice),
+            x1: u64::conditional_select(&a.x1, &b.x1, choice),
+            x0: u64::conditional_select(&a.x0, &b.x0, choice),
+        }
+    }
+
+    fn conditional_swap(a: &mut Fe25519, b: &mut Fe25519, choice: Choice) {
+        u64::conditional_swap(&mut a.x3, &mut b.x3, choice);
+        u64::conditional_swap(&mut a.x2, &mut b.x2, choice);
+        u64::conditional_swap(&mut a.x1, &mut b.x1, choice);
+        u64::conditional_swap(&mut a.x0, &mut b.x0, choice);
+    }
+}
+
+impl Fe25519 {
+    pub const ZERO: Fe25519 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 0 };
+    pub const ONE: Fe25519 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 1 };
+
+    /// Replaces the value with its negation mod p when `choice` is set, in constant time.
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let mut negated = Fe25519::default();
+        fe_sub(&mut negated, &Fe25519::default(), self);
+        self.conditional_assign(&negated, choice);
+    }
+
+    /// RFC 7748 decodeUCoordinate: reads 32 little-endian bytes, masks bit 255 and reduces
+    /// non-canonical values (2**255 - 19 and above) modulo p.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Fe25519 {
+        let temp = Fe25519 { x3: le_u64(bytes, 3) & UMASK63, x2: le_u64(bytes, 2), x1: le_u64(bytes, 1), x0: le_u64(bytes, 0) };
+        fe_reduce(&temp)
+    }
+
+    /// As `from_bytes`, but rejects (rather than reduces) encodings of 2**255 - 19 and above,
+    /// including any with bit 255 set.
+    pub fn try_from_canonical_bytes(bytes: &[u8; 32]) -> CtOption<Fe25519> {
+        let temp = Fe25519 { x3: le_u64(bytes, 3), x2: le_u64(bytes, 2), x1: le_u64(bytes, 1), x0: le_u64(bytes, 0) };
+        let is_canonical = temp.is_canonical();
+        CtOption::new(fe_reduce(&temp), is_canonical)
+    }
+
+    /// Constant-time check that the value is fully reduced, i.e. less than 2**255 - 19.
+    pub fn is_canonical(&self) -> Choice {
+        // Adding 19 reaches bit 255 (or beyond) exactly when the value is at least 2**255 - 19
+        let x0_roll_19 = u128::from(self.x0) + 19;
+        let x1_roll_19 = (x0_roll_19 >> 64) + u128::from(self.x1);
+        let x2_roll_19 = (x1_roll_19 >> 64) + u128::from(self.x2);
+        let x3_roll_19 = (x2_roll_19 >> 64) + u128::from(self.x3);
+        let high = (x3_roll_19 >> 63) as u64;
+        let nonzero = (high | high.wrapping_neg()) >> 63;
+        Choice::from((1 ^ nonzero) as u8)
+    }
+
+    /// RFC 7748 encodeUCoordinate: the canonical (fully reduced) value as 32 little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let temp = fe_reduce(self);
+        let mut result = [0u8; 32];
+        result[0..8].copy_from_slice(&temp.x0.to_le_bytes());
+        result[8..16].copy_from_slice(&temp.x1.to_le_bytes());
+        result[16..24].copy_from_slice(&temp.x2.to_le_bytes());
+        result[24..32].copy_from_slice(&temp.x3.to_le_bytes());
+        result
+    }
+}
+
+/// Parses a u-coordinate from "0x" followed by 64 hex digits (optionally dash-separated).
+pub fn try_get_u(src: &str) -> Result<Fe25519, ParseError> {
+    Ok(Fe25519::from_bytes(&hex_to_bytes(src)?))
+}
+
+/// Parses and clamps a scalar from "0x" followed by 64 hex digits (optionally dash-separated).
+pub fn try_get_k(src: &str) -> Result<Fe25519, ParseError> {
+    Ok(decode_scalar(&hex_to_bytes(src)?))
+}
+
+/// As `try_get_u`, but panics on malformed input.
+pub fn get_u(src: &str) -> Fe25519 {
+    try_get_u(src).unwrap()
+}
+
+/// As `try_get_k`, but panics on malformed input.
 pub fn get_k(src: &str) -> Fe25519 {
-    let new_k = format!("{}", src);
-    let temp1 = Fe25519::from_str(&new_k).unwrap();
-    let mut temp2 = Fe25519 { x3: u64::from_be(temp1.x0), x2: u64::from_be(temp1.x1), x1: u64::from_be(temp1.x2), x0: u64::from_be(temp1.x3) };
-    temp2.x0 = temp2.x0 & 0xFFFF_FFFF_FFFF_FFF8;
-    temp2.x3 = temp2.x3 & 0x7FFF_FFFF_FFFF_FFFF;
-    temp2.x3 = temp2.x3 | 0x4000_0000_0000_0000;
-    debug_assert!(check_size(&temp2));
-    temp2
+    try_get_k(src).unwrap()
+}
+
+// RFC 7748 decodeScalar25519: little-endian bytes, clear the 3 low bits, clear bit 255 and set bit 254
+pub(crate) fn decode_scalar(src: &[u8; 32]) -> Fe25519 {
+    let mut temp = Fe25519 { x3: le_u64(src, 3), x2: le_u64(src, 2), x1: le_u64(src, 1), x0: le_u64(src, 0) };
+    temp.x0 &= 0xFFFF_FFFF_FFFF_FFF8;
+    temp.x3 &= 0x7FFF_FFFF_FFFF_FFFF;
+    temp.x3 |= 0x4000_0000_0000_0000;
+    temp
+}
+
+fn le_u64(src: &[u8; 32], index: usize) -> u64 {
+    let mut limb = [0u8; 8];
+    limb.copy_from_slice(&src[index * 8..index * 8 + 8]);
+    u64::from_le_bytes(limb)
+}
+
+// Fully reduce any 256-bit value; the arithmetic below only ever sees canonical inputs
+fn fe_reduce(src: &Fe25519) -> Fe25519 {
+    // Fold bit 255 back in with 2**255 = 19 mod p; result is < 2**255 + 19
+    let x0_inc_19 = u128::from(src.x0) + u128::from(src.x3 >> 63) * 19;
+    let inc_x0 = x0_inc_19 as u64;
+    let x1_inc_19 = u128::from(src.x1) + (x0_inc_19 >> 64);
+    let inc_x1 = x1_inc_19 as u64;
+    let x2_inc_19 = u128::from(src.x2) + (x1_inc_19 >> 64);
+    let inc_x2 = x2_inc_19 as u64;
+    let x3_inc_19 = u128::from(src.x3 & UMASK63) + (x2_inc_19 >> 64);
+    let inc_x3 = x3_inc_19 as u64;
+
+    // Anything at or above 2**255 - 19 rolls over bit 255 when incremented by 19
+    let x0_roll_19 = u128::from(inc_x0) + 19;
+    let x1_roll_19 = (x0_roll_19 >> 64) + u128::from(inc_x1);
+    let x2_roll_19 = (x1_roll_19 >> 64) + u128::from(inc_x2);
+    let x3_roll_19 = (x2_roll_19 >> 64) + u128::from(inc_x3);
+    let rollover = 0u64.overflowing_sub((x3_roll_19 >> 63) as u64).0; // extend 1111... or 0000...
+
+    Fe25519 {
+        x3: UMASK63 & (!rollover & inc_x3 | rollover & (x3_roll_19 as u64)),
+        x2: !rollover & inc_x2 | rollover & (x2_roll_19 as u64),
+        x1: !rollover & inc_x1 | rollover & (x1_roll_19 as u64),
+        x0: !rollover & inc_x0 | rollover & (x0_roll_19 as u64),
+    }
 }
 
 pub(crate) fn fe_add(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
     // Check the inputs are less than 2**255 - 19
-    debug_assert!(check_size(src1));
-    debug_assert!(check_size(src2));
+    debug_assert!(bool::from(src1.is_canonical()));
+    debug_assert!(bool::from(src2.is_canonical()));
 
     // Add while propagating carry; Max sum will be < 2**256 - 38
     let x0_add_x0 = u128::from(src1.x0) + u128::from(src2.x0);
@@ -97,12 +223,12 @@ pub(crate) fn fe_add(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
     dest.x0 = !rollover & inc_x0 | rollover & roll_x0;
 
     // Check the output is less than 2**255 - 19
-    debug_assert!(check_size(dest));
+    debug_assert!(bool::from(dest.is_canonical()));
 }
 
 pub(crate) fn fe_sub(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
-    debug_assert!(check_size(&src1));
-    debug_assert!(check_size(&src2));
+    debug_assert!(bool::from(src1.is_canonical()));
+    debug_assert!(bool::from(src2.is_canonical()));
 
     let x0_sub_x0 = u128::from(src1.x0).overflowing_sub(u128::from(src2.x0)).0; // .0 is result
     let sub_x0 = x0_sub_x0 as u64;
@@ -116,19 +242,19 @@ pub(crate) fn fe_sub(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
     // If carry set, we decrement another 19
     let x0_dec_19 = u128::from(sub_x0).overflowing_sub(u128::from(sub_x3 >> 63) * 19).0;
     dest.x0 = x0_dec_19 as u64;
-    let x1_dec_19 = (x0_dec_19 >> 64) + u128::from(sub_x1);
+    let x1_dec_19 = u128::from(sub_x1).overflowing_sub(x0_dec_19 >> 127).0; // borrow, as above
     dest.x1 = x1_dec_19 as u64;
-    let x2_dec_19 = (x1_dec_19 >> 64) + u128::from(sub_x2);
+    let x2_dec_19 = u128::from(sub_x2).overflowing_sub(x1_dec_19 >> 127).0;
     dest.x2 = x2_dec_19 as u64;
-    let x3_dec_19 = (x2_dec_19 >> 64) + u128::from(sub_x3);
+    let x3_dec_19 = u128::from(sub_x3).overflowing_sub(x2_dec_19 >> 127).0;
     dest.x3 = (x3_dec_19 as u64) & UMASK63;
 
-    debug_assert!(check_size(&dest));
+    debug_assert!(bool::from(dest.is_canonical()));
 }
 
 pub fn fe_mul(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
-    debug_assert!(check_size(&src1));
-    debug_assert!(check_size(&src2));
+    debug_assert!(bool::from(src1.is_canonical()));
+    debug_assert!(bool::from(src2.is_canonical()));
 
     // for each src1.X multiply and sum src2 (4 'paragraphs')
     let x0_mul_x0 = u128::from(src1.x0) * u128::from(src2.x0);
@@ -182,14 +308,14 @@ pub fn fe_mul(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
     let red_x30 = x30_red_38 as u64;
 
     // If MSB is set add 19
-    let x00_inc_19 = u128::from(red_x00) + (x30_red_38 >> 63) as u128 * 19;
+    let x00_inc_19 = u128::from(red_x00) + (x30_red_38 >> 63) * 19;
     let inc_x00 = x00_inc_19 as u64;
     let x10_inc_19 = u128::from(red_x10) + (x00_inc_19 >> 64);
     let inc_x10 = x10_inc_19 as u64;
     let x20_inc_19 = u128::from(red_x20) + (x10_inc_19 >> 64);
     let inc_x20 = x20_inc_19 as u64;
-    let x30_inc_19 = u128::from(red_x30) + (x20_inc_19 >> 64);
-    let inc_x30 = x30_inc_19 as u64 & UMASK63;
+    let x30_inc_19 = u128::from(red_x30 & UMASK63) + (x20_inc_19 >> 64);
+    let inc_x30 = x30_inc_19 as u64; // a carry into bit 255 is folded back by the rollover below
 
     // We could still be above 2**255 - 19; increment and see if we rollover
     let x00_roll_19 = u128::from(inc_x00) + 19;
@@ -204,12 +330,12 @@ pub fn fe_mul(dest: &mut Fe25519, src1: &Fe25519, src2: &Fe25519) {
     dest.x1 = !rollover & inc_x10 | rollover & (x10_roll_19 as u64);
     dest.x0 = !rollover & inc_x00 | rollover & (x00_roll_19 as u64);
 
-    debug_assert!(check_size(&dest));
+    debug_assert!(bool::from(dest.is_canonical()));
 }
 
 #[inline]
 pub(crate) fn fe_square(dest: &mut Fe25519, src: &Fe25519) {
-    debug_assert!(check_size(&dest));
+    debug_assert!(bool::from(src.is_canonical()));
 
     let x0_mul_x0 = u128::from(src.x0) * u128::from(src.x0);
     let x0_mul_x1 = u128::from(src.x0) * u128::from(src.x1);
@@ -245,18 +371,18 @@ pub(crate) fn fe_square(dest: &mut Fe25519, src: &Fe25519) {
     let red_x10 = x10_red_38 as u64;
     let x20_red_38 = u128::from(mul_2) + (u128::from(mul_6) * 38) + (x10_red_38 >> 64);
     let red_x20 = x20_red_38 as u64;
-    let x30_red_38 = u128::from(mul_3) + (u128::from(mul_7) * 38) + (x20_red_38 >> 64);
+    let x30_red_38 = u128::from(mul_3) + (mul_7 * 38) + (x20_red_38 >> 64);
     let red_x30 = x30_red_38 as u64;
 
     // If MSB is set add 19
-    let x00_inc_19 = u128::from(red_x00) + (x30_red_38 >> 63) as u128 * 19;
+    let x00_inc_19 = u128::from(red_x00) + (x30_red_38 >> 63) * 19;
     let inc_x00 = x00_inc_19 as u64;
     let x10_inc_19 = u128::from(red_x10) + (x00_inc_19 >> 64);
     let inc_x10 = x10_inc_19 as u64;
     let x20_inc_19 = u128::from(red_x20) + (x10_inc_19 >> 64);
     let inc_x20 = x20_inc_19 as u64;
-    let x30_inc_19 = u128::from(red_x30) + (x20_inc_19 >> 64);
-    let inc_x30 = x30_inc_19 as u64 & UMASK63;
+    let x30_inc_19 = u128::from(red_x30 & UMASK63) + (x20_inc_19 >> 64);
+    let inc_x30 = x30_inc_19 as u64; // a carry into bit 255 is folded back by the rollover below
 
     // We could still be above 2**255 - 19; increment and see if we rollover
     let x00_roll_19 = u128::from(inc_x00) + 19;
@@ -271,11 +397,11 @@ pub(crate) fn fe_square(dest: &mut Fe25519, src: &Fe25519) {
     dest.x1 = !rollover & inc_x10 | rollover & (x10_roll_19 as u64);
     dest.x0 = !rollover & inc_x00 | rollover & (x00_roll_19 as u64);
 
-    debug_assert!(check_size(&dest));
+    debug_assert!(bool::from(dest.is_canonical()));
 }
 
 pub(crate) fn fe_mul_121665(dest: &mut Fe25519, src: &Fe25519) {
-    debug_assert!(check_size(&dest));
+    debug_assert!(bool::from(src.is_canonical()));
 
     // multiply by 121665 and propagate carries
     let x0_mul_12 = u128::from(src.x0) * 121_665;
@@ -295,92 +421,73 @@ pub(crate) fn fe_mul_121665(dest: &mut Fe25519, src: &Fe25519) {
     let inc_x1 = x1_inc_19 as u64;
     let x2_inc_19 = u128::from(mul_x2) + (x1_inc_19 >> 64);
     let inc_x2 = x2_inc_19 as u64;
-    let x3_inc_19 = u128::from(mul_x3) + (x2_inc_19 >> 64);
+    let x3_inc_19 = u128::from(mul_x3 & UMASK63) + (x2_inc_19 >> 64);
     let inc_x3 = x3_inc_19 as u64;
 
-    dest.x3 = UMASK63 & inc_x3;
-    dest.x2 = inc_x2;
-    dest.x1 = inc_x1;
-    dest.x0 = inc_x0;
+    // We could still be above 2**255 - 19; increment and see if we rollover
+    let x0_roll_19 = u128::from(inc_x0) + 19;
+    let x1_roll_19 = (x0_roll_19 >> 64) + u128::from(inc_x1);
+    let x2_roll_19 = (x1_roll_19 >> 64) + u128::from(inc_x2);
+    let x3_roll_19 = (x2_roll_19 >> 64) + u128::from(inc_x3);
+    let rollover = 0u64.overflowing_sub((x3_roll_19 >> 63) as u64).0; // extend 1111... or 0000...
 
-    debug_assert!(check_size(&dest));
-}
+    // If no rollover take the original value, otherwise take the 'roll by 19'
+    dest.x3 = UMASK63 & (!rollover & inc_x3 | rollover & (x3_roll_19 as u64));
+    dest.x2 = !rollover & inc_x2 | rollover & (x2_roll_19 as u64);
+    dest.x1 = !rollover & inc_x1 | rollover & (x1_roll_19 as u64);
+    dest.x0 = !rollover & inc_x0 | rollover & (x0_roll_19 as u64);
 
-fn fe_cswap(swap: &Fe25519, x_2: &mut Fe25519, x_3: &mut Fe25519) {
-    let dummy = Fe25519 {
-        x3: swap.x3 & (x_2.x3 ^ x_3.x3),
-        x2: swap.x2 & (x_2.x2 ^ x_3.x2),
-        x1: swap.x1 & (x_2.x1 ^ x_3.x1),
-        x0: swap.x0 & (x_2.x0 ^ x_3.x0),
-    };
-    x_2.x3 ^= dummy.x3;
-    x_2.x2 ^= dummy.x2;
-    x_2.x1 ^= dummy.x1;
-    x_2.x0 ^= dummy.x0;
-    x_3.x3 ^= dummy.x3;
-    x_3.x2 ^= dummy.x2;
-    x_3.x1 ^= dummy.x1;
-    x_3.x0 ^= dummy.x0;
+    debug_assert!(bool::from(dest.is_canonical()));
 }
 
-// To be optimized away...
-fn k_t(k: &Fe25519, t: i16) -> Fe25519 {
-    //= (k >> t) & 1
-    let x0: u64;//= 0;
-    // match against range is experimental, so use if-else-etc
-    if t <= 63 {
-        x0 = k.x0 >> t as u64;
-    } else if (t >= 64) & (t <= 127) {
-        x0 = k.x1 >> (t as u64 - 64);
-    } else if (t >= 128) & (t <= 191) {
-        x0 = k.x2 >> (t as u64 - 128);
-    } else {
-        x0 = k.x3 >> (t as u64 - 192);
-    }
-
-    if (x0 & 01) != 0 {
-        Fe25519 { x3: 0xFFFFFFFFFFFFFFFF, x2: 0xFFFFFFFFFFFFFFFF, x1: 0xFFFFFFFFFFFFFFFF, x0: 0xFFFFFFFFFFFFFFFF }
-    } else {
-        Fe25519 { x3: 0, x2: 0, x1: 0, x0: 0 }
-    }
+// (k >> t) & 1 without branching on the secret bit; only the (public) limb index depends on t
+fn k_t(k: &Fe25519, t: i16) -> Choice {
+    let limb = match t / 64 {
+        0 => k.x0,
+        1 => k.x1,
+        2 => k.x2,
+        _ => k.x3,
+    };
+    Choice::from(((limb >> (t % 64)) & 1) as u8)
 }
 
 pub fn fe_invert(result: &mut Fe25519, z: &Fe25519) {
+    let mut xx: Fe25519; // scratch copy, as the destination may not alias a source
     let mut t0 = Fe25519::default();
-    fe_square(&mut t0, &z);
+    fe_square(&mut t0, z);
 
     /* t1 = t0 ** (2 ** 2) = z ** 8 */
     let mut t1 = Fe25519::default();
     fe_square(&mut t1, &t0);
-    let xx0 = t1;
-    fe_square(&mut t1, &xx0);
+    xx = t1;
+    fe_square(&mut t1, &xx);
 
     /* t1 = z * t1 = z ** 9 */
-    let xx1 = t1;
-    fe_mul(&mut t1, &z, &xx1);
+    xx = t1;
+    fe_mul(&mut t1, z, &xx);
     /* t0 = t0 * t1 = z ** 11 -- stash t0 away for the end. */
-    let xx2 = t0;
-    fe_mul(&mut t0, &xx2, &t1);
+    xx = t0;
+    fe_mul(&mut t0, &xx, &t1);
 
     /* t2 = t0 ** 2 = z ** 22 */
     let mut t2 = Fe25519::default();
     fe_square(&mut t2, &t0);
 
     /* t1 = t1 * t2 = z ** (2 ** 5 - 1) */
-    let xx3 = t1;
-    fe_mul(&mut t1, &xx3, &t2);
+    xx = t1;
+    fe_mul(&mut t1, &xx, &t2);
 
     /* t2 = t1 ** (2 ** 5) = z ** ((2 ** 5) * (2 ** 5 - 1)) */
     fe_square(&mut t2, &t1);
     for _i in 1..5 {
         //(i = 1; i < 5; ++i)
-        let xx4 = t2;
-        fe_square(&mut t2, &xx4);
+        xx = t2;
+        fe_square(&mut t2, &xx);
     }
 
     /* t1 = t1 * t2 = z ** ((2 ** 5 + 1) * (2 ** 5 - 1)) = z ** (2 ** 10 - 1) */
-    let xx5 = t1;
-    fe_mul(&mut t1, &t2, &xx5);
+    xx = t1;
+    fe_mul(&mut t1, &t2, &xx);
 
     /* Continuing similarly... */
 
@@ -388,87 +495,221 @@ pub fn fe_invert(result: &mut Fe25519, z: &Fe25519) {
     fe_square(&mut t2, &t1);
     for _i in 1..10 {
         // (i = 1; i < 10; ++i)
-        let xx6 = t2;
-        fe_square(&mut t2, &xx6);
+        xx = t2;
+        fe_square(&mut t2, &xx);
     }
 
-    let xx7 = t2;
-    fe_mul(&mut t2, &xx7, &t1);
+    xx = t2;
+    fe_mul(&mut t2, &xx, &t1);
 
     /* t2 = z ** (2 ** 40 - 1) */
     let mut t3 = Fe25519::default();
     fe_square(&mut t3, &t2);
     for _i in 1..20 {
         // (i = 1; i < 20; ++i)
-        let xx8 = t3;
-        fe_square(&mut t3, &xx8);
+        xx = t3;
+        fe_square(&mut t3, &xx);
     }
-    let xx9 = t2;
-    fe_mul(&mut t2, &t3, &xx9);
+    xx = t2;
+    fe_mul(&mut t2, &t3, &xx);
 
     /* t2 = z ** (2 ** 10) * (2 ** 40 - 1) */
     for _i in 0..10 {
         //(i = 0; i < 10; ++i)
-        let xx10 = t2;
-        fe_square(&mut t2, &xx10);
+        xx = t2;
+        fe_square(&mut t2, &xx);
     }
 
     /* t1 = z ** (2 ** 50 - 1) */
-    let xx11 = t1;
-    fe_mul(&mut t1, &t2, &xx11);
+    xx = t1;
+    fe_mul(&mut t1, &t2, &xx);
 
     /* t2 = z ** (2 ** 100 - 1) */
     fe_square(&mut t2, &t1);
     for _i in 1..50 {
         //(i = 1; i < 50; ++i)
-        let xx12 = t2;
-        fe_square(&mut t2, &xx12);
+        xx = t2;
+        fe_square(&mut t2, &xx);
     }
-    let xx13 = t2;
-    fe_mul(&mut t2, &xx13, &t1);
+    xx = t2;
+    fe_mul(&mut t2, &xx, &t1);
 
     /* t2 = z ** (2 ** 200 - 1) */
     fe_square(&mut t3, &t2);
     for _i in 1..100 {
         // (i = 1; i < 100; ++i)
-        let xx14 = t3;
-        fe_square(&mut t3, &xx14);
+        xx = t3;
+        fe_square(&mut t3, &xx);
     }
-    let xx15 = t2;
-    fe_mul(&mut t2, &t3, &xx15);
+    xx = t2;
+    fe_mul(&mut t2, &t3, &xx);
 
     /* t2 = z ** ((2 ** 50) * (2 ** 200 - 1) */
     for _i in 0..50 {
         // (i = 0; i < 50; ++i)
-        let xx16 = t2;
-        fe_square(&mut t2, &xx16);
+        xx = t2;
+        fe_square(&mut t2, &xx);
     }
 
     /* t1 = z ** (2 ** 250 - 1) */
-    let xx17 = t1;
-    fe_mul(&mut t1, &t2, &xx17);
+    xx = t1;
+    fe_mul(&mut t1, &t2, &xx);
 
     /* t1 = z ** ((2 ** 5) * (2 ** 250 - 1)) */
     for _i in 0..5 {
         // (i = 0; i < 5; ++i)
-        let xx18 = t1;
-        fe_square(&mut t1, &xx18);
+        xx = t1;
+        fe_square(&mut t1, &xx);
     }
     /* Recall t0 = z ** 11; out = z ** (2 ** 255 - 21) */
     let mut out = Fe25519::default();
     fe_mul(&mut out, &t1, &t0);
 
     *result = Fe25519 { ..out };
+
+    // z may be secret (e.g. the ladder's z_2), so scrub everything derived from it
+    xx.zeroize();
+    t0.zeroize();
+    t1.zeroize();
+    t2.zeroize();
+    t3.zeroize();
+    out.zeroize();
+}
+
+impl Fe25519 {
+    // Squares k times
+    pub(crate) fn pow2k(&self, k: u32) -> Fe25519 {
+        let mut result = self.square();
+        for _i in 1..k {
+            result = result.square();
+        }
+        result
+    }
+
+    /// Raises to the power (p - 5) / 8 = 2**252 - 3, the exponent behind square roots mod p.
+    pub fn pow22523(&self) -> Fe25519 {
+        let t0 = self.square(); //                  z ** 2
+        let t1 = t0.pow2k(2) * self; //             z ** 9
+        let t0 = t0 * t1; //                        z ** 11
+        let t0 = t0.square() * t1; //               z ** (2 ** 5 - 1)
+        let t0 = t0.pow2k(5) * t0; //               z ** (2 ** 10 - 1)
+        let t1 = t0.pow2k(10) * t0; //              z ** (2 ** 20 - 1)
+        let t1 = t1.pow2k(20) * t1; //              z ** (2 ** 40 - 1)
+        let t0 = t1.pow2k(10) * t0; //              z ** (2 ** 50 - 1)
+        let t1 = t0.pow2k(50) * t0; //              z ** (2 ** 100 - 1)
+        let t1 = t1.pow2k(100) * t1; //             z ** (2 ** 200 - 1)
+        let t0 = t1.pow2k(50) * t0; //              z ** (2 ** 250 - 1)
+        t0.pow2k(2) * self //                       z ** (2 ** 252 - 3)
+    }
+
+    /// The 'sign' of RFC 9496: set when the canonical encoding is odd.
+    pub fn is_negative(&self) -> Choice {
+        Choice::from(self.to_bytes()[0] & 1)
+    }
+
+    /// SQRT_RATIO_M1 of RFC 9496 section 4.2: returns (true, +sqrt(u/v)) when u/v is square,
+    /// (true, 0) when u is zero, (false, 0) when v is zero and u is not, and otherwise
+    /// (false, +sqrt(sqrt(-1) * u/v)). The root returned is always non-negative.
+    pub fn sqrt_ratio(u: &Fe25519, v: &Fe25519) -> (Choice, Fe25519) {
+        let v3 = v.square() * v;
+        let v7 = v3.square() * v;
+        let mut r = (u * v3) * (u * v7).pow22523();
+        let check = v * r.square();
+
+        let u_neg = -u;
+        let correct_sign_sqrt = check.ct_eq(u);
+        let flipped_sign_sqrt = check.ct_eq(&u_neg);
+        let flipped_sign_sqrt_i = check.ct_eq(&(u_neg * SQRT_M1));
+
+        let r_prime = SQRT_M1 * r;
+        r.conditional_assign(&r_prime, flipped_sign_sqrt | flipped_sign_sqrt_i);
+        let r_is_negative = r.is_negative();
+        r.conditional_negate(r_is_negative);
+
+        (correct_sign_sqrt | flipped_sign_sqrt, r)
+    }
+
+    /// Constant-time quadratic residue test; zero counts as a square.
+    pub fn is_square(&self) -> Choice {
+        Fe25519::sqrt_ratio(self, &Fe25519::ONE).0
+    }
+}
+
+impl Fe25519 {
+    /// Inverts every element in place with Montgomery's trick: one fe_invert plus 3(n-1)
+    /// multiplications. Zeros stay zero (as with fe_invert) without disturbing the others.
+    pub fn batch_invert(inputs: &mut [Fe25519]) {
+        // scratch[i] holds the product of all (nonzero) inputs before i
+        let mut scratch = vec![Fe25519::ONE; inputs.len()];
+        let mut acc = Fe25519::ONE;
+        for (input, prefix) in inputs.iter().zip(scratch.iter_mut()) {
+            *prefix = acc;
+            acc = Fe25519::conditional_select(&(acc * input), &acc, input.ct_eq(&Fe25519::ZERO));
+        }
+
+        acc = acc.invert();
+
+        // Walk back, peeling one input off the inverted product at a time
+        for (input, prefix) in inputs.iter_mut().rev().zip(scratch.iter().rev()) {
+            let is_zero = input.ct_eq(&Fe25519::ZERO);
+            let next_acc = Fe25519::conditional_select(&(acc * *input), &acc, is_zero);
+            *input = Fe25519::conditional_select(&(acc * prefix), &Fe25519::ZERO, is_zero);
+            acc = next_acc;
+        }
+
+        scratch.zeroize();
+        acc.zeroize();
+    }
 }
 
-#[allow(non_snake_case)]
 pub(crate) fn mul(result: &mut Fe25519, k: &Fe25519, u: Fe25519) {
+    let mut x_2 = Fe25519::default();
+    let mut z_2 = Fe25519::default();
+    ladder(&mut x_2, &mut z_2, k, u);
+
+    let mut t000 = Fe25519::default();
+    fe_invert(&mut t000, &z_2);
+
+    let mut out = Fe25519::default();
+    fe_mul(&mut out, &x_2, &t000);
+
+    *result = Fe25519 { ..out };
+
+    x_2.zeroize();
+    z_2.zeroize();
+    t000.zeroize();
+    out.zeroize();
+}
+
+// As mul for many (k, u) pairs, but the ladders' projective results share a single inversion
+pub(crate) fn mul_batch(results: &mut [Fe25519], k: &[Fe25519], u: &[Fe25519]) {
+    assert_eq!(results.len(), k.len());
+    assert_eq!(results.len(), u.len());
+    let mut x_2 = vec![Fe25519::default(); results.len()];
+    let mut z_2 = vec![Fe25519::default(); results.len()];
+    for i in 0..results.len() {
+        ladder(&mut x_2[i], &mut z_2[i], &k[i], u[i]);
+    }
+
+    Fe25519::batch_invert(&mut z_2);
+
+    for i in 0..results.len() {
+        fe_mul(&mut results[i], &x_2[i], &z_2[i]);
+    }
+
+    x_2.zeroize();
+    z_2.zeroize();
+}
+
+// The RFC 7748 Montgomery ladder, stopping short of the final inversion: u(k * u) = x_out / z_out
+#[allow(non_snake_case)]
+fn ladder(x_out: &mut Fe25519, z_out: &mut Fe25519, k: &Fe25519, u: Fe25519) {
     let x_1 = u; // x_1 = u
     let mut x_2 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 1 }; // x_2 = 1
     let mut z_2 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 0 }; // z_2 = 0
     let mut x_3 = u; //x_3 = u
     let mut z_3 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 1 }; //z_3 = 1
-    let mut swap = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 0 }; //swap = 0
+    let mut swap = Choice::from(0); //swap = 0
     let mut A = Fe25519::default();
     let mut AA = Fe25519::default();
     let mut B = Fe25519::default();
@@ -486,13 +727,10 @@ pub(crate) fn mul(result: &mut Fe25519, k: &Fe25519, u: Fe25519) {
 
     for t in (0..=(255i16 - 1)).rev() {
         //                                                  For t = bits-1 down to 0:
-        let k_t = k_t(&k, t); //                       k_t = (k >> t) & 1
-        swap.x3 ^= k_t.x3; //                                   swap ^= k_t
-        swap.x2 ^= k_t.x2;
-        swap.x1 ^= k_t.x1;
-        swap.x0 ^= k_t.x0;
-        fe_cswap(&swap, &mut x_2, &mut x_3); //                 (x_2, x_3) = cswap(swap, x_2, x_3)
-        fe_cswap(&swap, &mut z_2, &mut z_3); //       (z_2, z_3) = cswap(swap, z_2, z_3)
+        let k_t = k_t(k, t); //                                 k_t = (k >> t) & 1
+        swap ^= k_t; //                                         swap ^= k_t
+        Fe25519::conditional_swap(&mut x_2, &mut x_3, swap); // (x_2, x_3) = cswap(swap, x_2, x_3)
+        Fe25519::conditional_swap(&mut z_2, &mut z_3, swap); // (z_2, z_3) = cswap(swap, z_2, z_3)
         swap = k_t; //                                          swap = k_t
 
         fe_add(&mut A, &x_2, &z_2); //          A = x_2 + z_2
@@ -514,14 +752,17 @@ pub(crate) fn mul(result: &mut Fe25519, k: &Fe25519, u: Fe25519) {
         fe_add(&mut t5, &AA, &t4); //           z_2 = E * (AA + a24 * E)
         fe_mul(&mut z_2, &E, &t5);
     }
-    fe_cswap(&swap, &mut x_2, &mut x_3); //                 (x_2, x_3) = cswap(swap, x_2, x_3)
-    fe_cswap(&swap, &mut z_2, &mut z_3); //       (z_2, z_3) = cswap(swap, z_2, z_3)
-
-    let mut t000 = Fe25519::default();
-    fe_invert(&mut t000, &z_2);
-
-    let mut out = Fe25519::default();
-    fe_mul(&mut out, &x_2, &t000);
-
-    *result = Fe25519 { ..out };
+    Fe25519::conditional_swap(&mut x_2, &mut x_3, swap); // (x_2, x_3) = cswap(swap, x_2, x_3)
+    Fe25519::conditional_swap(&mut z_2, &mut z_3, swap); // (z_2, z_3) = cswap(swap, z_2, z_3)
+
+    *x_out = x_2;
+    *z_out = z_2;
+
+    // Everything below depends on the secret scalar; scrub it before the stack frame is reused
+    for temp in [
+        &mut x_2, &mut z_2, &mut x_3, &mut z_3, &mut A, &mut AA, &mut B, &mut BB, &mut C, &mut CB, &mut D, &mut DA, &mut E,
+        &mut t1, &mut t2, &mut t3, &mut t4, &mut t5,
+    ] {
+        temp.zeroize();
+    }
 }
@@ -0,0 +1,357 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc7748 (sections 4.2 and 5)
+// Arithmetic mod p = 2**448 - 2**224 - 1 for X448. Seven saturated 64-bit limbs, every value
+// kept fully reduced; wide results are folded back with 2**448 = 2**224 + 1 mod p.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
+
+// p, least significant limb first
+const P: [u64; 7] = [
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFE_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+];
+
+#[derive(Clone, Copy, Default)]
+pub struct Fe448 {
+    // 7 * 64 = 448; limbs[0] is LSB
+    pub(crate) limbs: [u64; 7],
+}
+
+impl Zeroize for Fe448 {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl ConstantTimeEq for Fe448 {
+    fn ct_eq(&self, other: &Fe448) -> Choice {
+        self.limbs.ct_eq(&other.limbs)
+    }
+}
+
+impl PartialEq for Fe448 {
+    fn eq(&self, other: &Fe448) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Fe448 {}
+
+impl ConditionallySelectable for Fe448 {
+    fn conditional_select(a: &Fe448, b: &Fe448, choice: Choice) -> Fe448 {
+        let mut result = Fe448::default();
+        for i in 0..7 {
+            result.limbs[i] = u64::conditional_select(&a.limbs[i], &b.limbs[i], choice);
+        }
+        result
+    }
+
+    fn conditional_swap(a: &mut Fe448, b: &mut Fe448, choice: Choice) {
+        for i in 0..7 {
+            u64::conditional_swap(&mut a.limbs[i], &mut b.limbs[i], choice);
+        }
+    }
+}
+
+impl Fe448 {
+    pub const ZERO: Fe448 = Fe448 { limbs: [0, 0, 0, 0, 0, 0, 0] };
+    pub const ONE: Fe448 = Fe448 { limbs: [1, 0, 0, 0, 0, 0, 0] };
+
+    /// Replaces the value with its negation mod p when `choice` is set, in constant time.
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let mut negated = Fe448::default();
+        fe448_sub(&mut negated, &Fe448::ZERO, self);
+        self.conditional_assign(&negated, choice);
+    }
+
+    /// RFC 7748 decodeUCoordinate for X448: 56 little-endian bytes, with values of p and above
+    /// reduced mod p.
+    pub fn from_bytes(bytes: &[u8; 56]) -> Fe448 {
+        let mut limbs = [0u64; 7];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[8 * i..8 * i + 8]);
+            *limb = u64::from_le_bytes(chunk);
+        }
+        // Below 2**448 < 2p, so one conditional subtraction is enough
+        Fe448 { limbs: subtract_p_if_needed(&limbs) }
+    }
+
+    /// As `from_bytes`, but rejects (rather than reduces) encodings of p and above.
+    pub fn try_from_canonical_bytes(bytes: &[u8; 56]) -> CtOption<Fe448> {
+        let candidate = Fe448::from_bytes(bytes);
+        CtOption::new(candidate, candidate.to_bytes().ct_eq(bytes))
+    }
+
+    /// RFC 7748 encodeUCoordinate: 56 little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; 56] {
+        let mut result = [0u8; 56];
+        for (i, limb) in self.limbs.iter().enumerate() {
+            result[8 * i..8 * i + 8].copy_from_slice(&limb.to_le_bytes());
+        }
+        result
+    }
+}
+
+// Subtracts p when the (below 2**448) input is at least p, keeping the difference only if it
+// did not borrow
+fn subtract_p_if_needed(limbs: &[u64; 7]) -> [u64; 7] {
+    let mut difference = [0u64; 7];
+    let mut borrow = 0u64;
+    for i in 0..7 {
+        let (d1, b1) = limbs[i].overflowing_sub(P[i]);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        difference[i] = d2;
+        borrow = u64::from(b1 | b2);
+    }
+    let keep_input = Choice::from(borrow as u8);
+    let mut result = [0u64; 7];
+    for i in 0..7 {
+        result[i] = u64::conditional_select(&difference[i], &limbs[i], keep_input);
+    }
+    result
+}
+
+// lo + hi * 2**448 = lo + hi + hi * 2**224 mod p, as an 11-limb sum; hi * 2**224 is hi shifted
+// up by three and a half limbs
+fn fold(lo: &[u64], hi: &[u64]) -> [u64; 11] {
+    let mut shifted = [0u64; 11];
+    for i in 0..7 {
+        shifted[i + 3] |= hi[i] << 32;
+        shifted[i + 4] |= hi[i] >> 32;
+    }
+    let mut result = [0u64; 11];
+    let mut carry: u128 = 0;
+    for i in 0..11 {
+        let lo_i = if i < 7 { lo[i] } else { 0 };
+        let hi_i = if i < 7 { hi[i] } else { 0 };
+        carry += u128::from(lo_i) + u128::from(hi_i) + u128::from(shifted[i]);
+        result[i] = carry as u64;
+        carry >>= 64;
+    }
+    result
+}
+
+// Fully reduces a value below 2**896. Each fold shrinks the excess over 2**448: below 2**673,
+// then 2**450, then 2**448 + 2**227, and the fourth fold cannot overflow. A fixed number of
+// folds keeps this constant time
+fn reduce_wide(wide: &[u64; 14]) -> Fe448 {
+    let mut folded = fold(&wide[..7], &wide[7..]);
+    for _i in 0..3 {
+        let mut hi = [0u64; 7];
+        hi[..4].copy_from_slice(&folded[7..]);
+        folded = fold(&folded[..7], &hi);
+    }
+    debug_assert!(folded[7..].iter().all(|&limb| limb == 0));
+    let mut limbs = [0u64; 7];
+    limbs.copy_from_slice(&folded[..7]);
+    Fe448 { limbs: subtract_p_if_needed(&limbs) }
+}
+
+pub(crate) fn fe448_add(dest: &mut Fe448, src1: &Fe448, src2: &Fe448) {
+    let mut wide = [0u64; 14];
+    let mut carry: u128 = 0;
+    for (limb, (a, b)) in wide.iter_mut().zip(src1.limbs.iter().zip(src2.limbs.iter())) {
+        carry += u128::from(*a) + u128::from(*b);
+        *limb = carry as u64;
+        carry >>= 64;
+    }
+    wide[7] = carry as u64;
+    *dest = reduce_wide(&wide);
+}
+
+pub(crate) fn fe448_sub(dest: &mut Fe448, src1: &Fe448, src2: &Fe448) {
+    let mut difference = [0u64; 7];
+    let mut borrow = 0u64;
+    for (limb, (a, b)) in difference.iter_mut().zip(src1.limbs.iter().zip(src2.limbs.iter())) {
+        let (d1, b1) = a.overflowing_sub(*b);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        *limb = d2;
+        borrow = u64::from(b1 | b2);
+    }
+    // On a borrow the limbs hold src1 - src2 + 2**448; taking away 2**448 - p = 2**224 + 1
+    // leaves src1 - src2 + p, which is in range
+    let mask = borrow.wrapping_neg();
+    let correction = [mask & 1, 0, 0, mask & (1 << 32), 0, 0, 0];
+    borrow = 0;
+    for (limb, (d, c)) in dest.limbs.iter_mut().zip(difference.iter().zip(correction.iter())) {
+        let (d1, b1) = d.overflowing_sub(*c);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        *limb = d2;
+        borrow = u64::from(b1 | b2);
+    }
+}
+
+// Schoolbook product into 14 limbs
+fn mul_wide(src1: &Fe448, src2: &Fe448) -> [u64; 14] {
+    let mut wide = [0u64; 14];
+    for i in 0..7 {
+        let mut carry: u128 = 0;
+        for j in 0..7 {
+            carry += u128::from(wide[i + j]) + u128::from(src1.limbs[i]) * u128::from(src2.limbs[j]);
+            wide[i + j] = carry as u64;
+            carry >>= 64;
+        }
+        wide[i + 7] = carry as u64;
+    }
+    wide
+}
+
+pub fn fe448_mul(dest: &mut Fe448, src1: &Fe448, src2: &Fe448) {
+    let mut wide = mul_wide(src1, src2);
+    *dest = reduce_wide(&wide);
+    wide.zeroize();
+}
+
+pub(crate) fn fe448_square(dest: &mut Fe448, src: &Fe448) {
+    let mut wide = mul_wide(src, src);
+    *dest = reduce_wide(&wide);
+    wide.zeroize();
+}
+
+// Multiplies by a24 = (156326 - 2) / 4 = 39081, the X448 ladder constant
+pub(crate) fn fe448_mul_39081(dest: &mut Fe448, src: &Fe448) {
+    let mut wide = [0u64; 14];
+    let mut carry: u128 = 0;
+    for (limb, a) in wide.iter_mut().zip(src.limbs.iter()) {
+        carry += u128::from(*a) * 39_081;
+        *limb = carry as u64;
+        carry >>= 64;
+    }
+    wide[7] = carry as u64;
+    *dest = reduce_wide(&wide);
+}
+
+impl Fe448 {
+    // Squares k times
+    pub(crate) fn pow2k(&self, k: u32) -> Fe448 {
+        let mut result = *self;
+        for _i in 0..k {
+            let temp = result;
+            fe448_square(&mut result, &temp);
+        }
+        result
+    }
+
+    /// Raises to the power (p - 3) / 4 = 2**446 - 2**222 - 1, the exponent behind square roots mod p.
+    pub fn pow_p_minus_3_div_4(&self) -> Fe448 {
+        let x1 = *self;
+        let x2 = x1.square() * x1; //                     z ** (2 ** 2 - 1)
+        let x3 = x2.square() * x1; //                     z ** (2 ** 3 - 1)
+        let x6 = x3.pow2k(3) * x3; //                     z ** (2 ** 6 - 1)
+        let x12 = x6.pow2k(6) * x6; //                    z ** (2 ** 12 - 1)
+        let x24 = x12.pow2k(12) * x12; //                 z ** (2 ** 24 - 1)
+        let x30 = x24.pow2k(6) * x6; //                   z ** (2 ** 30 - 1)
+        let x48 = x24.pow2k(24) * x24; //                 z ** (2 ** 48 - 1)
+        let x96 = x48.pow2k(48) * x48; //                 z ** (2 ** 96 - 1)
+        let x192 = x96.pow2k(96) * x96; //                z ** (2 ** 192 - 1)
+        let x222 = x192.pow2k(30) * x30; //               z ** (2 ** 222 - 1)
+        let x223 = x222.square() * x1; //                 z ** (2 ** 223 - 1)
+        x223.pow2k(223) * x222 //                         z ** ((2 ** 224 - 2) * 2 ** 222 + 2 ** 222 - 1)
+    }
+
+    /// The sign of RFC 8032 section 5.2.2: set when the canonical encoding is odd.
+    pub fn is_negative(&self) -> Choice {
+        Choice::from((self.limbs[0] & 1) as u8)
+    }
+
+    /// Returns (true, +sqrt(u/v)) when u/v is square and (true, 0) when u is zero; otherwise the
+    /// flag is false and the value meaningless. With p = 3 mod 4 the candidate root is
+    /// u^3 v (u^5 v^3)^((p - 3) / 4), as in RFC 8032 section 5.2.3. It is always non-negative.
+    pub fn sqrt_ratio(u: &Fe448, v: &Fe448) -> (Choice, Fe448) {
+        let u3v = u.square() * u * v;
+        let u5v3 = u3v * u.square() * v.square();
+        let mut r = u3v * u5v3.pow_p_minus_3_div_4();
+        let is_square = (v * r.square()).ct_eq(u);
+        let r_is_negative = r.is_negative();
+        r.conditional_negate(r_is_negative);
+        (is_square, r)
+    }
+}
+
+// z ** (p - 2), where p - 2 = 4 * ((p - 3) / 4) + 1
+pub fn fe448_invert(result: &mut Fe448, z: &Fe448) {
+    *result = z.pow_p_minus_3_div_4().pow2k(2) * z;
+}
+
+// RFC 7748 decodeScalar448: clear the 2 low bits and set bit 447
+pub(crate) fn decode_scalar448(src: &[u8; 56]) -> [u8; 56] {
+    let mut result = *src;
+    result[0] &= 252;
+    result[55] |= 128;
+    result
+}
+
+// u(k * u) for a clamped scalar k, by the RFC 7748 ladder over 448 bits
+#[allow(non_snake_case)]
+pub(crate) fn mul448(result: &mut Fe448, k: &[u8; 56], u: &Fe448) {
+    let x_1 = *u; //                                             x_1 = u
+    let mut x_2 = Fe448::ONE; //                                 x_2 = 1
+    let mut z_2 = Fe448::ZERO; //                                z_2 = 0
+    let mut x_3 = *u; //                                         x_3 = u
+    let mut z_3 = Fe448::ONE; //                                 z_3 = 1
+    let mut swap = Choice::from(0); //                           swap = 0
+    let mut A = Fe448::default();
+    let mut AA = Fe448::default();
+    let mut B = Fe448::default();
+    let mut BB = Fe448::default();
+    let mut C = Fe448::default();
+    let mut CB = Fe448::default();
+    let mut D = Fe448::default();
+    let mut DA = Fe448::default();
+    let mut E = Fe448::default();
+    let mut t1 = Fe448::default();
+    let mut t2 = Fe448::default();
+    let mut t3 = Fe448::default();
+    let mut t4 = Fe448::default();
+    let mut t5 = Fe448::default();
+
+    for t in (0..448).rev() {
+        //                                                  For t = bits-1 down to 0:
+        let k_t = Choice::from((k[t / 8] >> (t % 8)) & 1); //  k_t = (k >> t) & 1
+        swap ^= k_t; //                                         swap ^= k_t
+        Fe448::conditional_swap(&mut x_2, &mut x_3, swap); //   (x_2, x_3) = cswap(swap, x_2, x_3)
+        Fe448::conditional_swap(&mut z_2, &mut z_3, swap); //   (z_2, z_3) = cswap(swap, z_2, z_3)
+        swap = k_t; //                                          swap = k_t
+
+        fe448_add(&mut A, &x_2, &z_2); //       A = x_2 + z_2
+        fe448_square(&mut AA, &A); //           AA = A^2
+        fe448_sub(&mut B, &x_2, &z_2); //       B = x_2 - z_2
+        fe448_square(&mut BB, &B); //           BB = B^2
+        fe448_sub(&mut E, &AA, &BB); //         E = AA - BB
+        fe448_add(&mut C, &x_3, &z_3); //       C = x_3 + z_3
+        fe448_sub(&mut D, &x_3, &z_3); //       D = x_3 - z_3
+        fe448_mul(&mut DA, &D, &A); //          DA = D * A
+        fe448_mul(&mut CB, &C, &B); //          CB = C * B
+        fe448_add(&mut t1, &DA, &CB); //        x_3 = (DA + CB)^2
+        fe448_square(&mut x_3, &t1);
+        fe448_sub(&mut t2, &DA, &CB); //        z_3 = x_1 * (DA - CB)^2
+        fe448_square(&mut t3, &t2);
+        fe448_mul(&mut z_3, &x_1, &t3);
+        fe448_mul(&mut x_2, &AA, &BB); //       x_2 = AA * BB
+        fe448_mul_39081(&mut t4, &E);
+        fe448_add(&mut t5, &AA, &t4); //        z_2 = E * (AA + a24 * E)
+        fe448_mul(&mut z_2, &E, &t5);
+    }
+    Fe448::conditional_swap(&mut x_2, &mut x_3, swap); //   (x_2, x_3) = cswap(swap, x_2, x_3)
+    Fe448::conditional_swap(&mut z_2, &mut z_3, swap); //   (z_2, z_3) = cswap(swap, z_2, z_3)
+
+    let mut z_inv = Fe448::default();
+    fe448_invert(&mut z_inv, &z_2); //                      Return x_2 * (z_2^(p - 2))
+    fe448_mul(result, &x_2, &z_inv);
+
+    // Everything below depends on the secret scalar; scrub it before the stack frame is reused
+    for temp in [
+        &mut x_2, &mut z_2, &mut x_3, &mut z_3, &mut A, &mut AA, &mut B, &mut BB, &mut C, &mut CB, &mut D, &mut DA, &mut E,
+        &mut t1, &mut t2, &mut t3, &mut t4, &mut t5, &mut z_inv,
+    ] {
+        temp.zeroize();
+    }
+}
@@ -0,0 +1,122 @@
+#![deny(clippy::all)]
+
+// Fixed-base scalar multiplication, as in https://ed25519.cr.yp.to/ed25519-20110926.pdf (section 4):
+// the scalar is recoded into 64 signed radix-16 digits a_i in [-8, 8], and
+// [a]B = sum a_i 16^i B = 16 * (sum over odd i) + (sum over even i), where every a_i 16^i B is
+// fetched in constant time from a table of the multiples 1..8 of 256^j B. That leaves 64 mixed
+// additions and four doublings, against 255 ladder steps for a variable base.
+
+use std::sync::OnceLock;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::arith::Fe25519;
+use crate::edwards::{EdwardsPoint, ED25519_BASEPOINT, EDWARDS_D2};
+use crate::scalar::as_radix_16;
+
+// An affine point kept as (y + x, y - x, 2 d x y), the form the mixed addition consumes
+#[derive(Clone, Copy, Debug)]
+struct AffineNielsPoint {
+    y_plus_x: Fe25519,
+    y_minus_x: Fe25519,
+    xy2d: Fe25519,
+}
+
+impl AffineNielsPoint {
+    // The identity (0, 1)
+    const IDENTITY: AffineNielsPoint = AffineNielsPoint { y_plus_x: Fe25519::ONE, y_minus_x: Fe25519::ONE, xy2d: Fe25519::ZERO };
+
+    // Negating x swaps y + x with y - x and flips the sign of x y
+    fn conditional_negate(&mut self, choice: Choice) {
+        let (y_plus_x, y_minus_x) = (self.y_plus_x, self.y_minus_x);
+        self.y_plus_x.conditional_assign(&y_minus_x, choice);
+        self.y_minus_x.conditional_assign(&y_plus_x, choice);
+        self.xy2d.conditional_negate(choice);
+    }
+}
+
+impl ConditionallySelectable for AffineNielsPoint {
+    fn conditional_select(a: &AffineNielsPoint, b: &AffineNielsPoint, choice: Choice) -> AffineNielsPoint {
+        AffineNielsPoint {
+            y_plus_x: Fe25519::conditional_select(&a.y_plus_x, &b.y_plus_x, choice),
+            y_minus_x: Fe25519::conditional_select(&a.y_minus_x, &b.y_minus_x, choice),
+            xy2d: Fe25519::conditional_select(&a.xy2d, &b.xy2d, choice),
+        }
+    }
+}
+
+impl EdwardsPoint {
+    // madd-2008-hwcd-3: add_extended with the second point's Z = 1 and its products precomputed
+    fn add_affine_niels(&self, other: &AffineNielsPoint) -> EdwardsPoint {
+        let a = (self.y - self.x) * other.y_minus_x;
+        let b = (self.y + self.x) * other.y_plus_x;
+        let c = self.t * other.xy2d;
+        let d = self.z + self.z;
+        let e = b - a;
+        let f = d - c;
+        let g = d + c;
+        let h = b + a;
+        EdwardsPoint { x: e * f, y: g * h, z: f * g, t: e * h }
+    }
+}
+
+// Row j holds [1]P .. [8]P for P = 256^j B
+struct BasepointTable([[AffineNielsPoint; 8]; 32]);
+
+static BASEPOINT_TABLE: OnceLock<BasepointTable> = OnceLock::new();
+
+impl BasepointTable {
+    // Built on first use; all 256 points are normalized with a single batched inversion
+    fn get() -> &'static BasepointTable {
+        BASEPOINT_TABLE.get_or_init(|| {
+            let mut points = Vec::with_capacity(256);
+            let mut row_base = ED25519_BASEPOINT;
+            for _row in 0..32 {
+                let mut multiple = row_base;
+                for _column in 0..8 {
+                    points.push(multiple);
+                    multiple += &row_base;
+                }
+                row_base = row_base.mul_by_pow_2(8);
+            }
+            let mut z_inverses: Vec<Fe25519> = points.iter().map(|point| point.z).collect();
+            Fe25519::batch_invert(&mut z_inverses);
+            let mut table = BasepointTable([[AffineNielsPoint::IDENTITY; 8]; 32]);
+            for (index, (point, z_inverse)) in points.iter().zip(z_inverses.iter()).enumerate() {
+                let x = point.x * z_inverse;
+                let y = point.y * z_inverse;
+                table.0[index / 8][index % 8] = AffineNielsPoint { y_plus_x: y + x, y_minus_x: y - x, xy2d: x * y * EDWARDS_D2 };
+            }
+            table
+        })
+    }
+
+    // [digit] 256^row B for a digit in [-8, 8], reading every entry of the row
+    fn select(&self, row: usize, digit: i8) -> AffineNielsPoint {
+        let sign_mask = digit >> 7;
+        let magnitude = ((digit + sign_mask) ^ sign_mask) as u8;
+        let mut result = AffineNielsPoint::IDENTITY;
+        for (j, entry) in self.0[row].iter().enumerate() {
+            result.conditional_assign(entry, magnitude.ct_eq(&(j as u8 + 1)));
+        }
+        result.conditional_negate(Choice::from((sign_mask & 1) as u8));
+        result
+    }
+}
+
+// [scalar] B for any scalar below 2^255, in constant time
+pub(crate) fn mul_base_bits(scalar: &[u8; 32]) -> EdwardsPoint {
+    let table = BasepointTable::get();
+    let mut digits = as_radix_16(scalar);
+    let mut result = EdwardsPoint::identity();
+    for i in (1..64).step_by(2) {
+        result = result.add_affine_niels(&table.select(i / 2, digits[i]));
+    }
+    result = result.mul_by_pow_2(4);
+    for i in (0..64).step_by(2) {
+        result = result.add_affine_niels(&table.select(i / 2, digits[i]));
+    }
+    digits.zeroize();
+    result
+}
@@ -0,0 +1,158 @@
+#![deny(clippy::all)]
+
+// See: https://nacl.cr.yp.to/box.html and https://doc.libsodium.org/public-key_cryptography/sealed_boxes
+// crypto_box_curve25519xsalsa20poly1305 as NaCl and libsodium define it: the X25519 shared secret
+// is hashed with HSalsa20 into a key for XSalsa20-Poly1305. The first 32 bytes of the keystream
+// are the one-time Poly1305 key, and the "easy" layout puts the 16-byte tag before the ciphertext.
+// A sealed box is an anonymous crypto_box from a fresh key pair whose public half leads the output
+
+use std::fmt;
+
+use blake2::digest::consts::{U10, U24};
+use blake2::{Blake2b, Digest};
+use poly1305::universal_hash::generic_array::GenericArray;
+use poly1305::universal_hash::KeyInit;
+use poly1305::Poly1305;
+use rand::{CryptoRng, RngCore};
+use salsa20::cipher::{KeyIvInit, StreamCipher};
+use salsa20::{hsalsa, XSalsa20};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::keys::{KeyAgreementError, PublicKey, StaticSecret};
+
+/// The length of a crypto_box nonce.
+pub const CRYPTO_BOX_NONCEBYTES: usize = 24;
+
+/// The length of the Poly1305 tag that crypto_box_easy prepends to the ciphertext.
+pub const CRYPTO_BOX_MACBYTES: usize = 16;
+
+/// How much longer a sealed box is than its message: the ephemeral public key plus the tag.
+pub const CRYPTO_BOX_SEALBYTES: usize = 32 + CRYPTO_BOX_MACBYTES;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CryptoBoxError {
+    KeyAgreement(KeyAgreementError), // the X25519 output was all zeros
+    TooShort(usize),                 // the ciphertext cannot even hold the tag (and ephemeral key)
+    OpenError,                       // the tag did not verify
+}
+
+impl fmt::Display for CryptoBoxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CryptoBoxError::KeyAgreement(error) => write!(f, "key agreement failed: {}", error),
+            CryptoBoxError::TooShort(length) => write!(f, "ciphertext of {} bytes is too short", length),
+            CryptoBoxError::OpenError => write!(f, "decryption failed"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoBoxError {}
+
+impl From<KeyAgreementError> for CryptoBoxError {
+    fn from(error: KeyAgreementError) -> CryptoBoxError {
+        CryptoBoxError::KeyAgreement(error)
+    }
+}
+
+/// A fresh crypto_box key pair, in libsodium's (public, secret) order.
+pub fn crypto_box_keypair<T: RngCore + CryptoRng>(csprng: &mut T) -> (PublicKey, StaticSecret) {
+    let secret = StaticSecret::random_from_rng(csprng);
+    (PublicKey::from(&secret), secret)
+}
+
+/// The key shared by `sk` and the owner of `pk`: HSalsa20 keyed with their X25519 shared secret,
+/// over an all-zero input. Like libsodium, it refuses low-order public keys.
+pub fn crypto_box_beforenm(pk: &PublicKey, sk: &StaticSecret) -> Result<[u8; 32], CryptoBoxError> {
+    let shared_secret = sk.diffie_hellman_checked(pk)?;
+    let mut key = hsalsa::<U10>(GenericArray::from_slice(shared_secret.as_bytes()), &GenericArray::default());
+    let result = key.into();
+    key.zeroize();
+    Ok(result)
+}
+
+/// Encrypts `message` from `sk` to `pk`; the output is the tag followed by the ciphertext. A
+/// nonce must never be reused for the same pair of keys.
+pub fn crypto_box_easy(message: &[u8], nonce: &[u8; 24], pk: &PublicKey, sk: &StaticSecret) -> Result<Vec<u8>, CryptoBoxError> {
+    let mut key = crypto_box_beforenm(pk, sk)?;
+    let result = crypto_box_easy_afternm(message, nonce, &key);
+    key.zeroize();
+    Ok(result)
+}
+
+/// Decrypts and verifies the output of `crypto_box_easy` sent by `pk` to `sk`.
+pub fn crypto_box_open_easy(ciphertext: &[u8], nonce: &[u8; 24], pk: &PublicKey, sk: &StaticSecret) -> Result<Vec<u8>, CryptoBoxError> {
+    let mut key = crypto_box_beforenm(pk, sk)?;
+    let result = crypto_box_open_easy_afternm(ciphertext, nonce, &key);
+    key.zeroize();
+    result
+}
+
+/// `crypto_box_easy` with a key from `crypto_box_beforenm`, saving the X25519 work per message.
+pub fn crypto_box_easy_afternm(message: &[u8], nonce: &[u8; 24], key: &[u8; 32]) -> Vec<u8> {
+    let (mut cipher, mut mac_key) = xsalsa20_and_mac_key(nonce, key);
+    let mut result = vec![0u8; CRYPTO_BOX_MACBYTES];
+    result.extend_from_slice(message);
+    cipher.apply_keystream(&mut result[CRYPTO_BOX_MACBYTES..]);
+    let tag = Poly1305::new(GenericArray::from_slice(&mac_key)).compute_unpadded(&result[CRYPTO_BOX_MACBYTES..]);
+    result[..CRYPTO_BOX_MACBYTES].copy_from_slice(&tag);
+    mac_key.zeroize();
+    result
+}
+
+/// `crypto_box_open_easy` with a key from `crypto_box_beforenm`. Nothing is decrypted unless the
+/// tag verifies.
+pub fn crypto_box_open_easy_afternm(ciphertext: &[u8], nonce: &[u8; 24], key: &[u8; 32]) -> Result<Vec<u8>, CryptoBoxError> {
+    if ciphertext.len() < CRYPTO_BOX_MACBYTES {
+        return Err(CryptoBoxError::TooShort(ciphertext.len()));
+    }
+    let (mut cipher, mut mac_key) = xsalsa20_and_mac_key(nonce, key);
+    let (tag, body) = ciphertext.split_at(CRYPTO_BOX_MACBYTES);
+    let expected = Poly1305::new(GenericArray::from_slice(&mac_key)).compute_unpadded(body);
+    mac_key.zeroize();
+    if !bool::from(expected.as_slice().ct_eq(tag)) {
+        return Err(CryptoBoxError::OpenError);
+    }
+    let mut result = body.to_vec();
+    cipher.apply_keystream(&mut result);
+    Ok(result)
+}
+
+/// Encrypts `message` anonymously to `pk` under a fresh ephemeral key pair; only the holder of
+/// the matching secret key can open it, and nothing identifies the sender.
+pub fn crypto_box_seal<T: RngCore + CryptoRng>(message: &[u8], pk: &PublicKey, csprng: &mut T) -> Result<Vec<u8>, CryptoBoxError> {
+    crypto_box_seal_with(message, pk, &StaticSecret::random_from_rng(csprng))
+}
+
+/// Opens a sealed box addressed to the key pair (`pk`, `sk`).
+pub fn crypto_box_seal_open(ciphertext: &[u8], pk: &PublicKey, sk: &StaticSecret) -> Result<Vec<u8>, CryptoBoxError> {
+    if ciphertext.len() < CRYPTO_BOX_SEALBYTES {
+        return Err(CryptoBoxError::TooShort(ciphertext.len()));
+    }
+    let mut epk = [0u8; 32];
+    epk.copy_from_slice(&ciphertext[..32]);
+    let epk = PublicKey::from(epk);
+    crypto_box_open_easy(&ciphertext[32..], &seal_nonce(&epk, pk), &epk, sk)
+}
+
+// The sealed box under the given ephemeral secret: epk || crypto_box_easy(message) with the
+// nonce BLAKE2b-192(epk || pk)
+pub(crate) fn crypto_box_seal_with(message: &[u8], pk: &PublicKey, esk: &StaticSecret) -> Result<Vec<u8>, CryptoBoxError> {
+    let epk = PublicKey::from(esk);
+    let mut result = epk.to_bytes().to_vec();
+    result.extend_from_slice(&crypto_box_easy(message, &seal_nonce(&epk, pk), pk, esk)?);
+    Ok(result)
+}
+
+fn seal_nonce(epk: &PublicKey, pk: &PublicKey) -> [u8; 24] {
+    Blake2b::<U24>::new().chain_update(epk.as_bytes()).chain_update(pk.as_bytes()).finalize().into()
+}
+
+// XSalsa20 under the box key, with its first 32 bytes of keystream already taken as the
+// Poly1305 key
+fn xsalsa20_and_mac_key(nonce: &[u8; 24], key: &[u8; 32]) -> (XSalsa20, [u8; 32]) {
+    let mut cipher = XSalsa20::new(GenericArray::from_slice(key), GenericArray::from_slice(nonce));
+    let mut mac_key = [0u8; 32];
+    cipher.apply_keystream(&mut mac_key);
+    (cipher, mac_key)
+}
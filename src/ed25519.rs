@@ -0,0 +1,290 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc8032 (sections 5.1.5 - 5.1.7), PureEdDSA over edwards25519
+// plus the Ed25519ctx and Ed25519ph variants, which differ only in the dom2 prefix and prehashing
+
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::edwards::{EdwardsPoint, ED25519_BASEPOINT};
+use crate::scalar::Scalar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    MalformedPublicKey, // A does not decode to a curve point
+    MalformedR,         // R does not decode to a curve point
+    NonCanonicalS,      // S >= l
+    VerificationFailed,
+    InvalidContext(usize), // context length; at most 255 bytes, and Ed25519ctx needs at least one
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignatureError::MalformedPublicKey => write!(f, "public key is not a valid point encoding"),
+            SignatureError::MalformedR => write!(f, "signature R is not a valid point encoding"),
+            SignatureError::NonCanonicalS => write!(f, "signature S is not reduced mod l"),
+            SignatureError::VerificationFailed => write!(f, "signature does not verify"),
+            SignatureError::InvalidContext(length) => write!(f, "context of {} bytes is not allowed", length),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+// dom2(x, y) from RFC 8032 section 5.1; PureEdDSA uses the empty string instead
+fn dom2(phflag: u8, context: &[u8]) -> Result<Vec<u8>, SignatureError> {
+    if context.len() > 255 || (phflag == 0 && context.is_empty()) {
+        return Err(SignatureError::InvalidContext(context.len()));
+    }
+    let mut result = b"SigEd25519 no Ed25519 collisions".to_vec();
+    result.push(phflag);
+    result.push(context.len() as u8);
+    result.extend_from_slice(context);
+    Ok(result)
+}
+
+// SHA-512 of the concatenated parts, reduced mod l
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut digest: [u8; 64] = hasher.finalize().into();
+    let result = Scalar::from_bytes_mod_order_wide(&digest);
+    digest.zeroize();
+    result
+}
+
+/// The Ed25519ph prehash of a message: its SHA-512 digest.
+pub fn ed25519ph_prehash(message: &[u8]) -> [u8; 64] {
+    Sha512::digest(message).into()
+}
+
+/// An Ed25519 signature: the encoded point R followed by the little-endian scalar S.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: [u8; 32],
+    s: [u8; 32],
+}
+
+impl Signature {
+    /// Splits the 64 bytes without checking them; `VerifyingKey::verify` rejects bad R or S.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Signature {
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..]);
+        Signature { r, s }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut result = [0u8; 64];
+        result[..32].copy_from_slice(&self.r);
+        result[32..].copy_from_slice(&self.s);
+        result
+    }
+}
+
+/// An Ed25519 public key A, kept both encoded (it is hashed into every signature) and decoded.
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyingKey {
+    bytes: [u8; 32],
+    point: EdwardsPoint,
+}
+
+impl PartialEq for VerifyingKey {
+    fn eq(&self, other: &VerifyingKey) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for VerifyingKey {}
+
+impl VerifyingKey {
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<VerifyingKey, SignatureError> {
+        let point = EdwardsPoint::decompress(bytes).ok_or(SignatureError::MalformedPublicKey)?;
+        Ok(VerifyingKey { bytes: *bytes, point })
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// RFC 8032 section 5.1.7, with the cofactored check [8][S]B = [8]R + [8][k]A and a strict
+    /// S < l; a non-canonical S would otherwise make signatures malleable.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&[], message, signature)
+    }
+
+    /// Ed25519ctx verification; `context` must match the one used to sign.
+    pub fn verify_ctx(&self, message: &[u8], context: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom2(0, context)?, message, signature)
+    }
+
+    /// Ed25519ph verification of `prehash`, the output of `ed25519ph_prehash`.
+    pub fn verify_prehashed(&self, prehash: &[u8; 64], context: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom2(1, context)?, prehash, signature)
+    }
+
+    fn verify_with_dom(&self, dom: &[u8], message: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        let r = EdwardsPoint::decompress(&signature.r).ok_or(SignatureError::MalformedR)?;
+        let s: Option<Scalar> = Scalar::try_from_canonical_bytes(&signature.s).into();
+        let s = s.ok_or(SignatureError::NonCanonicalS)?;
+        let k = hash_to_scalar(&[dom, &signature.r, &self.bytes, message]);
+        let lhs = EdwardsPoint::mul_base(&s);
+        let rhs = r + self.point * &k;
+        if (lhs - rhs).is_small_order() {
+            Ok(())
+        } else {
+            Err(SignatureError::VerificationFailed)
+        }
+    }
+}
+
+/// An Ed25519 secret key: the 32-byte seed plus the expanded scalar s and nonce prefix.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; 32],
+    scalar: Scalar,
+    prefix: [u8; 32],
+    verifying_key: VerifyingKey,
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningKey {}
+
+impl Zeroize for SigningKey {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl SigningKey {
+    pub fn random_from_rng<T: RngCore + CryptoRng>(csprng: &mut T) -> SigningKey {
+        let mut seed = [0u8; 32];
+        csprng.fill_bytes(&mut seed);
+        let key = SigningKey::from_bytes(&seed);
+        seed.zeroize();
+        key
+    }
+
+    /// RFC 8032 section 5.1.5: hashes the seed, clamps the low half into s and keeps the high
+    /// half as the nonce prefix. Since B has order l, [s]B = [s mod l]B.
+    pub fn from_bytes(seed: &[u8; 32]) -> SigningKey {
+        let mut h: [u8; 64] = Sha512::digest(seed).into();
+        h[0] &= 248;
+        h[31] &= 127;
+        h[31] |= 64;
+        let mut clamped = [0u8; 32];
+        clamped.copy_from_slice(&h[..32]);
+        let scalar = Scalar::from_bytes_mod_order(&clamped);
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+        let point = EdwardsPoint::mul_base(&scalar);
+        let verifying_key = VerifyingKey { bytes: point.compress(), point };
+        h.zeroize();
+        clamped.zeroize();
+        SigningKey { seed: *seed, scalar, prefix, verifying_key }
+    }
+
+    /// The 32-byte seed.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// RFC 8032 section 5.1.6: deterministic, so signing the same message twice gives the same signature.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_with_dom(&[], message)
+    }
+
+    /// Ed25519ctx: binds the signature to a 1 to 255 byte `context`, so it cannot be replayed
+    /// under another context or as a plain Ed25519 signature.
+    pub fn sign_ctx(&self, message: &[u8], context: &[u8]) -> Result<Signature, SignatureError> {
+        Ok(self.sign_with_dom(&dom2(0, context)?, message))
+    }
+
+    /// Ed25519ph: signs `prehash`, the SHA-512 digest of the message that `ed25519ph_prehash`
+    /// computes; large messages can instead be hashed incrementally. The `context` may be empty.
+    pub fn sign_prehashed(&self, prehash: &[u8; 64], context: &[u8]) -> Result<Signature, SignatureError> {
+        Ok(self.sign_with_dom(&dom2(1, context)?, prehash))
+    }
+
+    fn sign_with_dom(&self, dom: &[u8], message: &[u8]) -> Signature {
+        let mut r = hash_to_scalar(&[dom, &self.prefix, message]);
+        let big_r = EdwardsPoint::mul_base(&r).compress();
+        let k = hash_to_scalar(&[dom, &big_r, &self.verifying_key.bytes, message]);
+        let mut s = r + k * self.scalar;
+        let signature = Signature { r: big_r, s: s.to_bytes() };
+        r.zeroize();
+        s.zeroize();
+        signature
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verifying_key.verify(message, signature)
+    }
+}
+
+/// Verifies many signatures at once, checking a random linear combination of their equations
+/// [8]([z_i][S_i]B - [z_i]R_i - [z_i k_i]A_i) = 0 with a single multiscalar multiplication. The
+/// 128-bit weights z_i come from `csprng`, so a forger cannot make invalid signatures cancel.
+/// When the combined check fails, each signature is verified on its own and the indices of
+/// the failing ones are returned.
+pub fn verify_batch<T: RngCore + CryptoRng>(
+    messages: &[&[u8]], signatures: &[Signature], verifying_keys: &[VerifyingKey], csprng: &mut T,
+) -> Result<(), Vec<usize>> {
+    assert_eq!(messages.len(), signatures.len(), "one signature is needed per message");
+    assert_eq!(messages.len(), verifying_keys.len(), "one verifying key is needed per message");
+    let mut scalars = vec![Scalar::ZERO];
+    let mut points = vec![ED25519_BASEPOINT];
+    let mut well_formed = true;
+    for ((message, signature), key) in messages.iter().zip(signatures).zip(verifying_keys) {
+        let r = EdwardsPoint::decompress(&signature.r);
+        let s: Option<Scalar> = Scalar::try_from_canonical_bytes(&signature.s).into();
+        let (r, s) = match (r, s) {
+            (Some(r), Some(s)) => (r, s),
+            _ => {
+                well_formed = false;
+                break;
+            }
+        };
+        let k = hash_to_scalar(&[&signature.r, &key.bytes, message]);
+        let mut z_bytes = [0u8; 32];
+        csprng.fill_bytes(&mut z_bytes[..16]);
+        let z = Scalar::try_from_canonical_bytes(&z_bytes).unwrap();
+        scalars[0] += z * s;
+        scalars.push(z);
+        points.push(-r);
+        scalars.push(z * k);
+        points.push(-key.point);
+    }
+    if well_formed && EdwardsPoint::vartime_multiscalar_mul(&scalars, &points).is_small_order() {
+        return Ok(());
+    }
+    let failures: Vec<usize> = (0..messages.len()).filter(|&i| verifying_keys[i].verify(messages[i], &signatures[i]).is_err()).collect();
+    if failures.is_empty() {
+        // Not expected: the combined equation holds whenever every cofactored equation does
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
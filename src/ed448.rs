@@ -0,0 +1,232 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc8032 (sections 5.2.5 - 5.2.7), Ed448 and Ed448ph over
+// edwards448. Both variants always hash a dom4 prefix, which carries the phflag and the context
+
+use rand::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ed25519::SignatureError;
+use crate::edwards448::Edwards448Point;
+use crate::scalar448::Scalar448;
+
+// dom4(x, y) from RFC 8032 section 5.2
+fn dom4(phflag: u8, context: &[u8]) -> Result<Vec<u8>, SignatureError> {
+    if context.len() > 255 {
+        return Err(SignatureError::InvalidContext(context.len()));
+    }
+    let mut result = b"SigEd448".to_vec();
+    result.push(phflag);
+    result.push(context.len() as u8);
+    result.extend_from_slice(context);
+    Ok(result)
+}
+
+// SHAKE256 of the concatenated parts, 114 bytes long
+fn shake256_114(parts: &[&[u8]]) -> [u8; 114] {
+    let mut hasher = Shake256::default();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut result = [0u8; 114];
+    hasher.finalize_xof().read(&mut result);
+    result
+}
+
+// The same digest, reduced mod l
+fn hash_to_scalar448(parts: &[&[u8]]) -> Scalar448 {
+    let mut digest = shake256_114(parts);
+    let result = Scalar448::from_bytes_mod_order_wide(&digest);
+    digest.zeroize();
+    result
+}
+
+/// The Ed448ph prehash of a message: its 64-byte SHAKE256 digest.
+pub fn ed448ph_prehash(message: &[u8]) -> [u8; 64] {
+    let mut hasher = Shake256::default();
+    hasher.update(message);
+    let mut result = [0u8; 64];
+    hasher.finalize_xof().read(&mut result);
+    result
+}
+
+/// An Ed448 signature: the encoded point R followed by the little-endian scalar S.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed448Signature {
+    r: [u8; 57],
+    s: [u8; 57],
+}
+
+impl Ed448Signature {
+    /// Splits the 114 bytes without checking them; `Ed448VerifyingKey::verify` rejects bad R or S.
+    pub fn from_bytes(bytes: &[u8; 114]) -> Ed448Signature {
+        let mut r = [0u8; 57];
+        let mut s = [0u8; 57];
+        r.copy_from_slice(&bytes[..57]);
+        s.copy_from_slice(&bytes[57..]);
+        Ed448Signature { r, s }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 114] {
+        let mut result = [0u8; 114];
+        result[..57].copy_from_slice(&self.r);
+        result[57..].copy_from_slice(&self.s);
+        result
+    }
+}
+
+/// An Ed448 public key A, kept both encoded (it is hashed into every signature) and decoded.
+#[derive(Clone, Copy, Debug)]
+pub struct Ed448VerifyingKey {
+    bytes: [u8; 57],
+    point: Edwards448Point,
+}
+
+impl PartialEq for Ed448VerifyingKey {
+    fn eq(&self, other: &Ed448VerifyingKey) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for Ed448VerifyingKey {}
+
+impl Ed448VerifyingKey {
+    pub fn from_bytes(bytes: &[u8; 57]) -> Result<Ed448VerifyingKey, SignatureError> {
+        let point = Edwards448Point::decompress(bytes).ok_or(SignatureError::MalformedPublicKey)?;
+        Ok(Ed448VerifyingKey { bytes: *bytes, point })
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 57] {
+        &self.bytes
+    }
+
+    pub fn to_bytes(&self) -> [u8; 57] {
+        self.bytes
+    }
+
+    /// RFC 8032 section 5.2.7 with an empty context, checking [4][S]B = [4]R + [4][k]A and a
+    /// strict S < l.
+    pub fn verify(&self, message: &[u8], signature: &Ed448Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom4(0, &[])?, message, signature)
+    }
+
+    /// Ed448 verification under a context of at most 255 bytes; it must match the one used to sign.
+    pub fn verify_ctx(&self, message: &[u8], context: &[u8], signature: &Ed448Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom4(0, context)?, message, signature)
+    }
+
+    /// Ed448ph verification of `prehash`, the output of `ed448ph_prehash`.
+    pub fn verify_prehashed(&self, prehash: &[u8; 64], context: &[u8], signature: &Ed448Signature) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom4(1, context)?, prehash, signature)
+    }
+
+    fn verify_with_dom(&self, dom: &[u8], message: &[u8], signature: &Ed448Signature) -> Result<(), SignatureError> {
+        let r = Edwards448Point::decompress(&signature.r).ok_or(SignatureError::MalformedR)?;
+        let s: Option<Scalar448> = Scalar448::try_from_canonical_bytes(&signature.s).into();
+        let s = s.ok_or(SignatureError::NonCanonicalS)?;
+        let k = hash_to_scalar448(&[dom, &signature.r, &self.bytes, message]);
+        let lhs = Edwards448Point::mul_base(&s);
+        let rhs = r + self.point * &k;
+        if (lhs - rhs).is_small_order() {
+            Ok(())
+        } else {
+            Err(SignatureError::VerificationFailed)
+        }
+    }
+}
+
+/// An Ed448 secret key: the 57-byte seed plus the expanded scalar s and nonce prefix.
+#[derive(Clone)]
+pub struct Ed448SigningKey {
+    seed: [u8; 57],
+    scalar: Scalar448,
+    prefix: [u8; 57],
+    verifying_key: Ed448VerifyingKey,
+}
+
+impl Drop for Ed448SigningKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Ed448SigningKey {}
+
+impl Zeroize for Ed448SigningKey {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl Ed448SigningKey {
+    pub fn random_from_rng<T: RngCore + CryptoRng>(csprng: &mut T) -> Ed448SigningKey {
+        let mut seed = [0u8; 57];
+        csprng.fill_bytes(&mut seed);
+        let key = Ed448SigningKey::from_bytes(&seed);
+        seed.zeroize();
+        key
+    }
+
+    /// RFC 8032 section 5.2.5: hashes the seed to 114 bytes, clamps the low half into s and
+    /// keeps the high half as the nonce prefix. Since B has order l, [s]B = [s mod l]B.
+    pub fn from_bytes(seed: &[u8; 57]) -> Ed448SigningKey {
+        let mut h = shake256_114(&[seed]);
+        h[0] &= 252;
+        h[55] |= 128;
+        h[56] = 0;
+        let mut clamped = [0u8; 57];
+        clamped.copy_from_slice(&h[..57]);
+        let scalar = Scalar448::from_bytes_mod_order(&clamped);
+        let mut prefix = [0u8; 57];
+        prefix.copy_from_slice(&h[57..]);
+        let point = Edwards448Point::mul_base(&scalar);
+        let verifying_key = Ed448VerifyingKey { bytes: point.compress(), point };
+        h.zeroize();
+        clamped.zeroize();
+        Ed448SigningKey { seed: *seed, scalar, prefix, verifying_key }
+    }
+
+    /// The 57-byte seed.
+    pub fn to_bytes(&self) -> [u8; 57] {
+        self.seed
+    }
+
+    pub fn verifying_key(&self) -> Ed448VerifyingKey {
+        self.verifying_key
+    }
+
+    /// RFC 8032 section 5.2.6 with an empty context; deterministic, like Ed25519.
+    pub fn sign(&self, message: &[u8]) -> Ed448Signature {
+        self.sign_with_dom(&dom4(0, &[]).expect("an empty context is always allowed"), message)
+    }
+
+    /// Ed448 under a `context` of at most 255 bytes, binding the signature to it.
+    pub fn sign_ctx(&self, message: &[u8], context: &[u8]) -> Result<Ed448Signature, SignatureError> {
+        Ok(self.sign_with_dom(&dom4(0, context)?, message))
+    }
+
+    /// Ed448ph: signs `prehash`, the 64-byte SHAKE256 digest of the message that
+    /// `ed448ph_prehash` computes. The `context` may be empty.
+    pub fn sign_prehashed(&self, prehash: &[u8; 64], context: &[u8]) -> Result<Ed448Signature, SignatureError> {
+        Ok(self.sign_with_dom(&dom4(1, context)?, prehash))
+    }
+
+    fn sign_with_dom(&self, dom: &[u8], message: &[u8]) -> Ed448Signature {
+        let mut r = hash_to_scalar448(&[dom, &self.prefix, message]);
+        let big_r = Edwards448Point::mul_base(&r).compress();
+        let k = hash_to_scalar448(&[dom, &big_r, &self.verifying_key.bytes, message]);
+        let mut s = r + k * self.scalar;
+        let signature = Ed448Signature { r: big_r, s: s.to_bytes() };
+        r.zeroize();
+        s.zeroize();
+        signature
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Ed448Signature) -> Result<(), SignatureError> {
+        self.verifying_key.verify(message, signature)
+    }
+}
@@ -0,0 +1,268 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc8032 (section 5.1) and https://eprint.iacr.org/2008/522
+// Twisted Edwards curve -x^2 + y^2 = 1 + d x^2 y^2, birationally equivalent to Curve25519
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::arith::Fe25519;
+use crate::basepoint_table::mul_base_bits;
+use crate::scalar::Scalar;
+
+// d = -121665 / 121666
+pub(crate) const EDWARDS_D: Fe25519 = Fe25519 { x3: 0x5203_6CEE_2B6F_FE73, x2: 0x8CC7_4079_7779_E898, x1: 0x0070_0A4D_4141_D8AB, x0: 0x75EB_4DCA_1359_78A3 };
+pub(crate) const EDWARDS_D2: Fe25519 = Fe25519 { x3: 0x2406_D9DC_56DF_FCE7, x2: 0x198E_80F2_EEF3_D130, x1: 0x00E0_149A_8283_B156, x0: 0xEBD6_9B94_26B2_F159 };
+
+/// The Ed25519 base point B, with y = 4/5 and non-negative x; it maps to u = 9 on Curve25519.
+pub const ED25519_BASEPOINT: EdwardsPoint = EdwardsPoint {
+    x: Fe25519 { x3: 0x2169_36D3_CD6E_53FE, x2: 0xC0A4_E231_FDD6_DC5C, x1: 0x692C_C760_9525_A7B2, x0: 0xC956_2D60_8F25_D51A },
+    y: Fe25519 { x3: 0x6666_6666_6666_6666, x2: 0x6666_6666_6666_6666, x1: 0x6666_6666_6666_6666, x0: 0x6666_6666_6666_6658 },
+    z: Fe25519::ONE,
+    t: Fe25519 { x3: 0x6787_5F0F_D78B_7665, x2: 0x66EA_4E8E_64AB_E37D, x1: 0x20F0_9F80_7751_52F5, x0: 0x6DDE_8AB3_A5B7_DDA3 },
+};
+
+/// A point on edwards25519 in extended coordinates: x = X/Z, y = Y/Z and x * y = T/Z.
+#[derive(Clone, Copy, Debug)]
+pub struct EdwardsPoint {
+    pub(crate) x: Fe25519,
+    pub(crate) y: Fe25519,
+    pub(crate) z: Fe25519,
+    pub(crate) t: Fe25519,
+}
+
+impl EdwardsPoint {
+    pub fn identity() -> EdwardsPoint {
+        EdwardsPoint { x: Fe25519::ZERO, y: Fe25519::ONE, z: Fe25519::ONE, t: Fe25519::ZERO }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ct_eq(&EdwardsPoint::identity()).into()
+    }
+
+    // add-2008-hwcd-3 with a = -1; unified, so also correct when both points are equal
+    pub(crate) fn add_extended(&self, other: &EdwardsPoint) -> EdwardsPoint {
+        let a = (self.y - self.x) * (other.y - other.x);
+        let b = (self.y + self.x) * (other.y + other.x);
+        let c = self.t * EDWARDS_D2 * other.t;
+        let d = self.z * other.z;
+        let d = d + d;
+        let e = b - a;
+        let f = d - c;
+        let g = d + c;
+        let h = b + a;
+        EdwardsPoint { x: e * f, y: g * h, z: f * g, t: e * h }
+    }
+
+    // dbl-2008-hwcd with a = -1
+    pub fn double(&self) -> EdwardsPoint {
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = self.z.square() + self.z.square();
+        let h = a + b;
+        let e = h - (self.x + self.y).square();
+        let g = a - b;
+        let f = c + g;
+        EdwardsPoint { x: e * f, y: g * h, z: f * g, t: e * h }
+    }
+
+    // Computes [2^k] P
+    pub fn mul_by_pow_2(&self, k: u32) -> EdwardsPoint {
+        let mut result = *self;
+        for _i in 0..k {
+            result = result.double();
+        }
+        result
+    }
+
+    pub fn mul_by_cofactor(&self) -> EdwardsPoint {
+        self.mul_by_pow_2(3)
+    }
+
+    /// True for the eight points of order dividing the cofactor, including the identity.
+    pub fn is_small_order(&self) -> bool {
+        self.mul_by_cofactor().is_identity()
+    }
+
+    // Constant-time double-and-add-always over all 256 bits of a little-endian scalar
+    pub(crate) fn mul_bits(&self, scalar: &[u8; 32]) -> EdwardsPoint {
+        let mut result = EdwardsPoint::identity();
+        for i in (0..256).rev() {
+            result = result.double();
+            let bit = Choice::from((scalar[i / 8] >> (i % 8)) & 1);
+            result.conditional_assign(&(result + self), bit);
+        }
+        result
+    }
+
+    /// Multiplies by an X25519-style clamped scalar, so that `to_montgomery` of the result
+    /// matches `x25519(bytes, u)` where u is the Montgomery form of this point.
+    pub fn mul_clamped(&self, mut bytes: [u8; 32]) -> EdwardsPoint {
+        bytes[0] &= 248;
+        bytes[31] &= 127;
+        bytes[31] |= 64;
+        self.mul_bits(&bytes)
+    }
+
+    /// [scalar] B for the Ed25519 base point, using a precomputed table; constant time.
+    pub fn mul_base(scalar: &Scalar) -> EdwardsPoint {
+        mul_base_bits(&scalar.to_bytes())
+    }
+
+    /// `ED25519_BASEPOINT.mul_clamped(bytes)`, using the same table as `mul_base`.
+    pub fn mul_base_clamped(mut bytes: [u8; 32]) -> EdwardsPoint {
+        bytes[0] &= 248;
+        bytes[31] &= 127;
+        bytes[31] |= 64;
+        mul_base_bits(&bytes)
+    }
+
+    /// RFC 8032 section 5.1.2 encoding: y, with the sign (low bit) of x in bit 255.
+    pub fn compress(&self) -> [u8; 32] {
+        let z_inv = self.z.invert();
+        let x = self.x * z_inv;
+        let y = self.y * z_inv;
+        let mut result = y.to_bytes();
+        result[31] ^= x.is_negative().unwrap_u8() << 7;
+        result
+    }
+
+    /// RFC 8032 section 5.1.3 decoding; rejects y >= p, non-points and a negative zero x.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<EdwardsPoint> {
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7F;
+        let y: Option<Fe25519> = Fe25519::try_from_canonical_bytes(&y_bytes).into();
+        EdwardsPoint::from_y(&y?, Choice::from(bytes[31] >> 7))
+    }
+
+    // Recovers x from x^2 = (y^2 - 1) / (d y^2 + 1), choosing the root with the given sign
+    fn from_y(y: &Fe25519, sign: Choice) -> Option<EdwardsPoint> {
+        let yy = y.square();
+        let u = yy - Fe25519::ONE;
+        let v = yy * EDWARDS_D + Fe25519::ONE;
+        let (is_valid_y, mut x) = Fe25519::sqrt_ratio(&u, &v);
+        if !bool::from(is_valid_y) || (bool::from(x.ct_eq(&Fe25519::ZERO)) && bool::from(sign)) {
+            return None;
+        }
+        x.conditional_negate(sign);
+        Some(EdwardsPoint { x, y: *y, z: Fe25519::ONE, t: x * y })
+    }
+
+    /// The birational map to Curve25519: u = (1 + y) / (1 - y). The identity maps to u = 0.
+    pub fn to_montgomery(&self) -> Fe25519 {
+        (self.z + self.y) * (self.z - self.y).invert()
+    }
+
+    /// The inverse map y = (u - 1) / (u + 1); the u-coordinate alone leaves the sign of x to be
+    /// chosen. Fails for u = -1 and for u-coordinates on the twist.
+    pub fn from_montgomery(u: &Fe25519, sign: Choice) -> Option<EdwardsPoint> {
+        if bool::from(u.ct_eq(&-Fe25519::ONE)) {
+            return None;
+        }
+        let y = (u - Fe25519::ONE) * (u + Fe25519::ONE).invert();
+        EdwardsPoint::from_y(&y, sign)
+    }
+}
+
+impl ConstantTimeEq for EdwardsPoint {
+    // Compare x1 / z1 with x2 / z2 (and likewise y) without inverting
+    fn ct_eq(&self, other: &EdwardsPoint) -> Choice {
+        (self.x * other.z).ct_eq(&(other.x * self.z)) & (self.y * other.z).ct_eq(&(other.y * self.z))
+    }
+}
+
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &EdwardsPoint) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for EdwardsPoint {}
+
+impl ConditionallySelectable for EdwardsPoint {
+    fn conditional_select(a: &EdwardsPoint, b: &EdwardsPoint, choice: Choice) -> EdwardsPoint {
+        EdwardsPoint {
+            x: Fe25519::conditional_select(&a.x, &b.x, choice),
+            y: Fe25519::conditional_select(&a.y, &b.y, choice),
+            z: Fe25519::conditional_select(&a.z, &b.z, choice),
+            t: Fe25519::conditional_select(&a.t, &b.t, choice),
+        }
+    }
+}
+
+impl Add<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn add(self, other: &EdwardsPoint) -> EdwardsPoint {
+        self.add_extended(other)
+    }
+}
+
+impl Add<&EdwardsPoint> for EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn add(self, other: &EdwardsPoint) -> EdwardsPoint {
+        self.add_extended(other)
+    }
+}
+
+impl Add<EdwardsPoint> for EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn add(self, other: EdwardsPoint) -> EdwardsPoint {
+        self.add_extended(&other)
+    }
+}
+
+impl AddAssign<&EdwardsPoint> for EdwardsPoint {
+    fn add_assign(&mut self, other: &EdwardsPoint) {
+        *self = self.add_extended(other);
+    }
+}
+
+impl Neg for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint { x: -self.x, y: self.y, z: self.z, t: -self.t }
+    }
+}
+
+impl Neg for EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn neg(self) -> EdwardsPoint {
+        -&self
+    }
+}
+
+impl Sub<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn sub(self, other: &EdwardsPoint) -> EdwardsPoint {
+        self.add_extended(&-other)
+    }
+}
+
+impl Sub<EdwardsPoint> for EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn sub(self, other: EdwardsPoint) -> EdwardsPoint {
+        self.add_extended(&-other)
+    }
+}
+
+impl SubAssign<&EdwardsPoint> for EdwardsPoint {
+    fn sub_assign(&mut self, other: &EdwardsPoint) {
+        *self = self.add_extended(&-other);
+    }
+}
+
+// Scalar multiplication runs in constant time over the full 256-bit encoding
+impl Mul<&Scalar> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn mul(self, scalar: &Scalar) -> EdwardsPoint {
+        self.mul_bits(&scalar.to_bytes())
+    }
+}
+
+impl Mul<&Scalar> for EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn mul(self, scalar: &Scalar) -> EdwardsPoint {
+        self.mul_bits(&scalar.to_bytes())
+    }
+}
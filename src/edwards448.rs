@@ -0,0 +1,251 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc8032 (sections 5.2.1 - 5.2.4)
+// The untwisted Edwards curve x^2 + y^2 = 1 + d x^2 y^2 with d = -39081, edwards448 ("Goldilocks"),
+// which is 4-isogenous to Curve448. d is not a square, so the addition law is complete
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::arith448::{fe448_mul_39081, Fe448};
+use crate::scalar448::Scalar448;
+
+/// The Ed448 base point B of RFC 8032 section 5.2, with non-negative x.
+pub const ED448_BASEPOINT: Edwards448Point = Edwards448Point {
+    x: Fe448 {
+        limbs: [
+            0x2626_A82B_C70C_C05E,
+            0x433B_80E1_8B00_938E,
+            0x12AE_1AF7_2AB6_6511,
+            0xEA6D_E324_A3D3_A464,
+            0x9E14_6570_470F_1767,
+            0x221D_15A6_22BF_36DA,
+            0x4F19_70C6_6BED_0DED,
+        ],
+    },
+    y: Fe448 {
+        limbs: [
+            0x9808_795B_F230_FA14,
+            0xFDBD_132C_4ED7_C8AD,
+            0x3AD3_FF1C_E67C_39C4,
+            0x8778_9C1E_05A0_C2D7,
+            0x4BEA_7373_6CA3_9840,
+            0x8876_2037_56C9_C762,
+            0x693F_4671_6EB6_BC24,
+        ],
+    },
+    z: Fe448::ONE,
+};
+
+// d * src = -(39081 * src)
+fn mul_by_d(src: &Fe448) -> Fe448 {
+    let mut result = Fe448::default();
+    fe448_mul_39081(&mut result, src);
+    -result
+}
+
+/// A point on edwards448 in projective coordinates: x = X/Z and y = Y/Z.
+#[derive(Clone, Copy, Debug)]
+pub struct Edwards448Point {
+    pub(crate) x: Fe448,
+    pub(crate) y: Fe448,
+    pub(crate) z: Fe448,
+}
+
+impl Edwards448Point {
+    pub fn identity() -> Edwards448Point {
+        Edwards448Point { x: Fe448::ZERO, y: Fe448::ONE, z: Fe448::ONE }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ct_eq(&Edwards448Point::identity()).into()
+    }
+
+    // The projective addition of RFC 8032 section 5.2.4
+    pub(crate) fn add_projective(&self, other: &Edwards448Point) -> Edwards448Point {
+        let a = self.z * other.z;
+        let b = a.square();
+        let c = self.x * other.x;
+        let d = self.y * other.y;
+        let e = mul_by_d(&(c * d));
+        let f = b - e;
+        let g = b + e;
+        let h = (self.x + self.y) * (other.x + other.y);
+        Edwards448Point { x: a * f * (h - c - d), y: a * g * (d - c), z: f * g }
+    }
+
+    // The doubling of RFC 8032 section 5.2.4
+    pub fn double(&self) -> Edwards448Point {
+        let b = (self.x + self.y).square();
+        let c = self.x.square();
+        let d = self.y.square();
+        let e = c + d;
+        let h = self.z.square();
+        let j = e - (h + h);
+        Edwards448Point { x: (b - e) * j, y: e * (c - d), z: e * j }
+    }
+
+    // Computes [2^k] P
+    pub fn mul_by_pow_2(&self, k: u32) -> Edwards448Point {
+        let mut result = *self;
+        for _i in 0..k {
+            result = result.double();
+        }
+        result
+    }
+
+    pub fn mul_by_cofactor(&self) -> Edwards448Point {
+        self.mul_by_pow_2(2)
+    }
+
+    /// True for the four points of order dividing the cofactor, including the identity.
+    pub fn is_small_order(&self) -> bool {
+        self.mul_by_cofactor().is_identity()
+    }
+
+    // Constant-time double-and-add-always over the low 448 bits of a little-endian scalar
+    pub(crate) fn mul_bits(&self, scalar: &[u8; 57]) -> Edwards448Point {
+        debug_assert!(scalar[56] == 0);
+        let mut result = Edwards448Point::identity();
+        for i in (0..448).rev() {
+            result = result.double();
+            let bit = Choice::from((scalar[i / 8] >> (i % 8)) & 1);
+            result.conditional_assign(&(result + self), bit);
+        }
+        result
+    }
+
+    /// [scalar] B for the Ed448 base point; constant time.
+    pub fn mul_base(scalar: &Scalar448) -> Edwards448Point {
+        ED448_BASEPOINT.mul_bits(&scalar.to_bytes())
+    }
+
+    /// RFC 8032 section 5.2.2 encoding: y in 56 bytes, then a byte holding the sign of x in its
+    /// top bit.
+    pub fn compress(&self) -> [u8; 57] {
+        let z_inv = self.z.invert();
+        let x = self.x * z_inv;
+        let y = self.y * z_inv;
+        let mut result = [0u8; 57];
+        result[..56].copy_from_slice(&y.to_bytes());
+        result[56] = x.is_negative().unwrap_u8() << 7;
+        result
+    }
+
+    /// RFC 8032 section 5.2.3 decoding; rejects y >= p, stray bits in the last byte, non-points
+    /// and a negative zero x.
+    pub fn decompress(bytes: &[u8; 57]) -> Option<Edwards448Point> {
+        if bytes[56] & 0x7F != 0 {
+            return None;
+        }
+        let mut y_bytes = [0u8; 56];
+        y_bytes.copy_from_slice(&bytes[..56]);
+        let y: Option<Fe448> = Fe448::try_from_canonical_bytes(&y_bytes).into();
+        let y = y?;
+        // x^2 = (y^2 - 1) / (d y^2 - 1); the denominator never vanishes as d is not a square
+        let yy = y.square();
+        let (is_valid_y, mut x) = Fe448::sqrt_ratio(&(yy - Fe448::ONE), &(mul_by_d(&yy) - Fe448::ONE));
+        let sign = Choice::from(bytes[56] >> 7);
+        if !bool::from(is_valid_y) || (bool::from(x.ct_eq(&Fe448::ZERO)) && bool::from(sign)) {
+            return None;
+        }
+        x.conditional_negate(sign);
+        Some(Edwards448Point { x, y, z: Fe448::ONE })
+    }
+}
+
+impl ConstantTimeEq for Edwards448Point {
+    // Compare x1 / z1 with x2 / z2 (and likewise y) without inverting
+    fn ct_eq(&self, other: &Edwards448Point) -> Choice {
+        (self.x * other.z).ct_eq(&(other.x * self.z)) & (self.y * other.z).ct_eq(&(other.y * self.z))
+    }
+}
+
+impl PartialEq for Edwards448Point {
+    fn eq(&self, other: &Edwards448Point) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Edwards448Point {}
+
+impl ConditionallySelectable for Edwards448Point {
+    fn conditional_select(a: &Edwards448Point, b: &Edwards448Point, choice: Choice) -> Edwards448Point {
+        Edwards448Point {
+            x: Fe448::conditional_select(&a.x, &b.x, choice),
+            y: Fe448::conditional_select(&a.y, &b.y, choice),
+            z: Fe448::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+impl Add<&Edwards448Point> for &Edwards448Point {
+    type Output = Edwards448Point;
+    fn add(self, other: &Edwards448Point) -> Edwards448Point {
+        self.add_projective(other)
+    }
+}
+
+impl Add<&Edwards448Point> for Edwards448Point {
+    type Output = Edwards448Point;
+    fn add(self, other: &Edwards448Point) -> Edwards448Point {
+        self.add_projective(other)
+    }
+}
+
+impl Add<Edwards448Point> for Edwards448Point {
+    type Output = Edwards448Point;
+    fn add(self, other: Edwards448Point) -> Edwards448Point {
+        self.add_projective(&other)
+    }
+}
+
+impl AddAssign<&Edwards448Point> for Edwards448Point {
+    fn add_assign(&mut self, other: &Edwards448Point) {
+        *self = self.add_projective(other);
+    }
+}
+
+impl Neg for &Edwards448Point {
+    type Output = Edwards448Point;
+    fn neg(self) -> Edwards448Point {
+        Edwards448Point { x: -self.x, y: self.y, z: self.z }
+    }
+}
+
+impl Neg for Edwards448Point {
+    type Output = Edwards448Point;
+    fn neg(self) -> Edwards448Point {
+        -&self
+    }
+}
+
+impl Sub<&Edwards448Point> for &Edwards448Point {
+    type Output = Edwards448Point;
+    fn sub(self, other: &Edwards448Point) -> Edwards448Point {
+        self.add_projective(&-other)
+    }
+}
+
+impl Sub<Edwards448Point> for Edwards448Point {
+    type Output = Edwards448Point;
+    fn sub(self, other: Edwards448Point) -> Edwards448Point {
+        self.add_projective(&-other)
+    }
+}
+
+// Scalar multiplication runs in constant time over the full 448 bits
+impl Mul<&Scalar448> for &Edwards448Point {
+    type Output = Edwards448Point;
+    fn mul(self, scalar: &Scalar448) -> Edwards448Point {
+        self.mul_bits(&scalar.to_bytes())
+    }
+}
+
+impl Mul<&Scalar448> for Edwards448Point {
+    type Output = Edwards448Point;
+    fn mul(self, scalar: &Scalar448) -> Edwards448Point {
+        self.mul_bits(&scalar.to_bytes())
+    }
+}
@@ -0,0 +1,97 @@
+#![deny(clippy::all)]
+
+// See: https://elligator.cr.yp.to/elligator-20130828.pdf (section 5) and
+// https://www.rfc-editor.org/rfc/rfc9380 (section 6.7.1), with the non-square Z = 2
+// Elligator 2 maps field elements onto Curve25519 and back, so that public keys can be sent as
+// strings indistinguishable from random
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConditionallySelectable, CtOption};
+use zeroize::Zeroize;
+
+use crate::arith::Fe25519;
+use crate::edwards::EdwardsPoint;
+use crate::keys::{PublicKey, StaticSecret};
+
+// The Montgomery curve coefficient A in v^2 = u^3 + A u^2 + u
+pub(crate) const MONTGOMERY_A: Fe25519 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 486_662 };
+
+// An Edwards point of order 8; adding a multiple of it leaves every X25519 result unchanged
+const EIGHT_TORSION: EdwardsPoint = EdwardsPoint {
+    x: Fe25519 { x3: 0x1FD5_B9A0_0639_4A28, x2: 0xE933_9932_38DE_4ABB, x1: 0x5C19_3C70_13E5_E238, x0: 0xDEA1_4646_C545_D14A },
+    y: Fe25519 { x3: 0x7A03_AC92_77FD_C74E, x2: 0xC6CC_392C_FA53_202A, x1: 0x0F67_100D_760B_3CBA, x0: 0x4FD8_4D3D_706A_17C7 },
+    z: Fe25519::ONE,
+    t: Fe25519 { x3: 0x6CE2_44C3_60A2_6BEB, x2: 0x3032_76D1_92F8_AF0A, x1: 0xBA99_3D74_CDFA_8596, x0: 0x2D0D_253E_DE7E_8781 },
+};
+
+/// The Elligator 2 map: sends any field element r to a point (u, v) on Curve25519, with
+/// u = -A / (1 + 2 r^2) when that lies on the curve and -u - A otherwise. The sign of v follows
+/// RFC 9380: odd for the first case, even for the second. r and -r give the same point.
+pub fn elligator2_map(r: &Fe25519) -> (Fe25519, Fe25519) {
+    // 2 is not a square, so 1 + 2 r^2 is never zero
+    let w = -MONTGOMERY_A * (r.square() + r.square() + Fe25519::ONE).invert();
+    let gw = w * (w.square() + MONTGOMERY_A * w + Fe25519::ONE);
+    let w_on_curve = gw.is_square();
+    let u = Fe25519::conditional_select(&(-w - MONTGOMERY_A), &w, w_on_curve);
+    let gu = u * (u.square() + MONTGOMERY_A * u + Fe25519::ONE);
+    let (_, mut v) = Fe25519::sqrt_ratio(&gu, &Fe25519::ONE);
+    v.conditional_negate(w_on_curve);
+    (u, v)
+}
+
+/// The inverse map: the r in [0, (p - 1) / 2] with `elligator2_map(r) = (u, v)`, where only the
+/// sign of v is needed. Exists for about half of all points, those with -2 u (u + A) square.
+pub fn elligator2_inverse(u: &Fe25519, v_is_negative: Choice) -> CtOption<Fe25519> {
+    // Odd v came from the first case, so r^2 = -(u + A) / 2u; even v from r^2 = -u / 2(u + A)
+    let u_plus_a = u + MONTGOMERY_A;
+    let numerator = Fe25519::conditional_select(&-u, &-u_plus_a, v_is_negative);
+    let denominator = Fe25519::conditional_select(&u_plus_a, u, v_is_negative);
+    let (is_square, mut r) = Fe25519::sqrt_ratio(&numerator, &(denominator + denominator));
+    // 2r mod p is odd exactly when r > (p - 1) / 2; the smaller root fits in 254 bits
+    r.conditional_negate((r + r).is_negative());
+    CtOption::new(r, is_square)
+}
+
+/// Decodes a 32-byte representative, ignoring its two (random) high bits, and maps it to the
+/// public key it hides.
+pub fn elligator2_public_key(representative: &[u8; 32]) -> PublicKey {
+    let mut bytes = *representative;
+    bytes[31] &= 0x3F;
+    let (u, _) = elligator2_map(&Fe25519::from_bytes(&bytes));
+    PublicKey::from(u.to_bytes())
+}
+
+/// Encodes a public key as a representative, if it has one. Bit 0 of `tweak` picks between
+/// the two representatives of the key, and bits 6 and 7 become the otherwise constant high
+/// bits, so a random tweak makes the output uniform over all 32-byte strings.
+pub fn elligator2_representative(public: &PublicKey, tweak: u8) -> Option<[u8; 32]> {
+    let u = Fe25519::from_bytes(public.as_bytes());
+    let r: Option<Fe25519> = elligator2_inverse(&u, Choice::from(tweak & 1)).into();
+    let mut bytes = r?.to_bytes();
+    bytes[31] |= tweak & 0xC0;
+    Some(bytes)
+}
+
+/// Generates a secret whose public key has a representative, returning the secret and a
+/// random representative. Keys made by `PublicKey::from` always lie in the prime-order
+/// subgroup, which would give them away; here the public key also carries a random low-order
+/// component, which the clamped scalar of any peer removes again in `diffie_hellman`.
+pub fn elligator2_keypair<T: RngCore + CryptoRng>(csprng: &mut T) -> (StaticSecret, [u8; 32]) {
+    loop {
+        let mut bytes = [0u8; 32];
+        let mut tweak = [0u8; 1];
+        csprng.fill_bytes(&mut bytes);
+        csprng.fill_bytes(&mut tweak);
+        let mut low_order = [0u8; 32];
+        low_order[0] = (tweak[0] >> 1) & 7;
+        let point = EdwardsPoint::mul_base_clamped(bytes) + EIGHT_TORSION.mul_bits(&low_order);
+        let public = PublicKey::from(point.to_montgomery().to_bytes());
+        if let Some(representative) = elligator2_representative(&public, tweak[0]) {
+            let secret = StaticSecret::from(bytes);
+            bytes.zeroize();
+            return (secret, representative);
+        }
+        bytes.zeroize();
+    }
+}
+
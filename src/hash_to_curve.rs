@@ -0,0 +1,170 @@
+#![deny(clippy::all)]
+
+// See: https://www.rfc-editor.org/rfc/rfc9380 (sections 5, 6.7.1, 6.8.2 and appendix D)
+// The suites curve25519_XMD:SHA-512_ELL2_RO_ / _NU_ and edwards25519_XMD:SHA-512_ELL2_RO_ / _NU_:
+// expand_message_xmd with SHA-512, hash_to_field with L = 48, Elligator 2 onto Curve25519, the
+// rational map onto edwards25519 and clearing of the cofactor h = 8. Every suite rejects an
+// empty domain separation tag, which section 3.1 forbids
+
+use std::fmt;
+
+use sha2::{Digest, Sha512};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::arith::Fe25519;
+use crate::edwards::EdwardsPoint;
+use crate::elligator::elligator2_map;
+
+// sqrt(-486664), the root with sgn0 = 0, which the rational map of appendix D scales v by
+const SQRT_MINUS_A_MINUS_TWO: Fe25519 = Fe25519 { x3: 0x0F26_EDF4_60A0_06BB, x2: 0xD27B_08DC_03FC_4F7E, x1: 0xC5A1_D3D1_4B7D_1A82, x0: 0xCC6E_04AA_FF45_7E06 };
+
+// 2^256 mod p
+const TWO_POW_256: Fe25519 = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 38 };
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashToCurveError {
+    EmptyDst,       // section 3.1 requires a non-empty domain separation tag
+    TooLong(usize), // requested length in bytes; at most 255 SHA-512 blocks, 255 * 64 bytes
+}
+
+impl fmt::Display for HashToCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HashToCurveError::EmptyDst => write!(f, "the domain separation tag must not be empty"),
+            HashToCurveError::TooLong(length) => write!(f, "{} bytes is more than 255 SHA-512 blocks can expand to", length),
+        }
+    }
+}
+
+impl std::error::Error for HashToCurveError {}
+
+/// expand_message_xmd of RFC 9380 section 5.3.1 with SHA-512: stretches `msg` into
+/// `len_in_bytes` uniform bytes bound to the domain separation tag `dst`. Tags longer than 255
+/// bytes are first hashed down as section 5.3.3 describes. Fails if `dst` is empty or
+/// `len_in_bytes` exceeds 255 * 64, the most that 255 SHA-512 blocks hold.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, HashToCurveError> {
+    if dst.is_empty() {
+        return Err(HashToCurveError::EmptyDst);
+    }
+    if len_in_bytes > 255 * 64 {
+        return Err(HashToCurveError::TooLong(len_in_bytes));
+    }
+    let hashed_dst: Vec<u8>;
+    let dst = if dst.len() > 255 {
+        hashed_dst = Sha512::new().chain_update(b"H2C-OVERSIZE-DST-").chain_update(dst).finalize().to_vec();
+        &hashed_dst[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+    let b_0 = Sha512::new()
+        .chain_update([0u8; 128])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    // ell = ceil(len_in_bytes / 64) blocks, at most 255 so the counter fits its one byte
+    let ell = len_in_bytes.div_ceil(64);
+    let mut result = Vec::with_capacity(ell * 64);
+    let mut b_i = [0u8; 64];
+    for i in 1..=ell as u8 {
+        // b_1 = H(b_0 || 1 || DST'), then b_i = H((b_0 xor b_(i-1)) || i || DST')
+        for (byte, b_0_byte) in b_i.iter_mut().zip(b_0.iter()) {
+            *byte ^= b_0_byte;
+        }
+        let digest = Sha512::new().chain_update(b_i).chain_update([i]).chain_update(dst).chain_update(dst_len).finalize();
+        b_i.copy_from_slice(&digest);
+        result.extend_from_slice(&b_i);
+    }
+    result.truncate(len_in_bytes);
+    b_i.zeroize();
+    Ok(result)
+}
+
+/// hash_to_field of RFC 9380 section 5.2: `count` field elements, each the reduction of 48
+/// big-endian bytes from `expand_message_xmd`, so their bias mod p is negligible. Fails if `dst`
+/// is empty or `count` exceeds 340, beyond which the 48-byte chunks outgrow `expand_message_xmd`.
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fe25519>, HashToCurveError> {
+    let mut uniform_bytes = expand_message_xmd(msg, dst, 48 * count)?;
+    let result = uniform_bytes.chunks(48).map(fe_from_wide_be_bytes).collect();
+    uniform_bytes.zeroize();
+    Ok(result)
+}
+
+// OS2IP mod p of 48 big-endian bytes. Reversed, the value is lo + 2^256 hi, with lo 256 bits
+// wide; from_bytes drops bit 255 of lo, which is worth 2^255 = 19 mod p
+fn fe_from_wide_be_bytes(bytes: &[u8]) -> Fe25519 {
+    let mut lo = [0u8; 32];
+    let mut hi = [0u8; 32];
+    for (i, byte) in bytes.iter().rev().enumerate() {
+        if i < 32 {
+            lo[i] = *byte;
+        } else {
+            hi[i - 32] = *byte;
+        }
+    }
+    let top_bit = Fe25519 { x3: 0, x2: 0, x1: 0, x0: 19 * u64::from(lo[31] >> 7) };
+    let result = Fe25519::from_bytes(&lo) + top_bit + Fe25519::from_bytes(&hi) * TWO_POW_256;
+    lo.zeroize();
+    hi.zeroize();
+    result
+}
+
+// The rational map of appendix D from (u, v) on Curve25519 to edwards25519:
+// x = sqrt(-486664) u / v and y = (u - 1) / (u + 1), in projective form so nothing is inverted.
+// The exceptional points v = 0 and u = -1 go to the identity
+fn montgomery_to_edwards(u: &Fe25519, v: &Fe25519) -> EdwardsPoint {
+    let u_minus_one = u - Fe25519::ONE;
+    let u_plus_one = u + Fe25519::ONE;
+    let c_u = SQRT_MINUS_A_MINUS_TWO * u;
+    let point = EdwardsPoint { x: c_u * u_plus_one, y: u_minus_one * v, z: v * u_plus_one, t: c_u * u_minus_one };
+    EdwardsPoint::conditional_select(&point, &EdwardsPoint::identity(), point.z.ct_eq(&Fe25519::ZERO))
+}
+
+// The inverse map: u = (1 + y) / (1 - y) and v = sqrt(-486664) u / x, sharing one inversion.
+// The identity, whose image is the point at infinity, comes out as (0, 0)
+fn edwards_to_montgomery(point: &EdwardsPoint) -> (Fe25519, Fe25519) {
+    let z_plus_y = point.z + point.y;
+    let inverse = ((point.z - point.y) * point.x).invert();
+    let u = z_plus_y * point.x * inverse;
+    let v = SQRT_MINUS_A_MINUS_TWO * z_plus_y * point.z * inverse;
+    (u, v)
+}
+
+// map_to_curve for the edwards25519 suites: Elligator 2, then the rational map
+fn map_to_edwards(r: &Fe25519) -> EdwardsPoint {
+    let (u, v) = elligator2_map(r);
+    montgomery_to_edwards(&u, &v)
+}
+
+/// edwards25519_XMD:SHA-512_ELL2_RO_: a random oracle onto the prime-order subgroup, the sum of
+/// two mapped field elements with the cofactor cleared. Fails only on an empty `dst`.
+pub fn hash_to_edwards25519(msg: &[u8], dst: &[u8]) -> Result<EdwardsPoint, HashToCurveError> {
+    let r = hash_to_field(msg, dst, 2)?;
+    Ok((map_to_edwards(&r[0]) + map_to_edwards(&r[1])).mul_by_cofactor())
+}
+
+/// edwards25519_XMD:SHA-512_ELL2_NU_: a single mapped field element with the cofactor cleared.
+/// Cheaper than `hash_to_edwards25519`, but its output is not uniformly distributed. Fails only
+/// on an empty `dst`.
+pub fn encode_to_edwards25519(msg: &[u8], dst: &[u8]) -> Result<EdwardsPoint, HashToCurveError> {
+    let r = hash_to_field(msg, dst, 1)?;
+    Ok(map_to_edwards(&r[0]).mul_by_cofactor())
+}
+
+/// curve25519_XMD:SHA-512_ELL2_RO_, returning (u, v). The point addition and cofactor clearing
+/// happen on the equivalent Edwards points. Fails only on an empty `dst`.
+pub fn hash_to_curve25519(msg: &[u8], dst: &[u8]) -> Result<(Fe25519, Fe25519), HashToCurveError> {
+    let r = hash_to_field(msg, dst, 2)?;
+    Ok(edwards_to_montgomery(&(map_to_edwards(&r[0]) + map_to_edwards(&r[1])).mul_by_cofactor()))
+}
+
+/// curve25519_XMD:SHA-512_ELL2_NU_, returning (u, v); not uniformly distributed. Fails only on
+/// an empty `dst`.
+pub fn encode_to_curve25519(msg: &[u8], dst: &[u8]) -> Result<(Fe25519, Fe25519), HashToCurveError> {
+    let r = hash_to_field(msg, dst, 1)?;
+    Ok(edwards_to_montgomery(&map_to_edwards(&r[0]).mul_by_cofactor()))
+}
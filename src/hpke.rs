@@ -0,0 +1,428 @@
+#![deny(clippy::all)]
+
+// See: https://www.rfc-editor.org/rfc/rfc9180 (sections 4 - 5.3 and 7)
+// Hybrid public key encryption with DHKEM(X25519, HKDF-SHA256) over this crate's X25519, any of
+// HKDF-SHA256/384/512 for the key schedule, and AES-128-GCM, AES-256-GCM or ChaCha20-Poly1305.
+// The four modes differ only in what goes into the key schedule: a pre-shared key, a sender
+// key pair, both or neither
+
+use std::fmt;
+
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::ChaCha20Poly1305;
+use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
+use sha2::{Sha256, Sha384, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::keys::{KeyAgreementError, PublicKey, StaticSecret};
+
+// DHKEM(X25519, HKDF-SHA256)
+const KEM_ID: u16 = 0x0020;
+
+// The nonce length Nn, which is 12 bytes for all three AEADs
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HpkeError {
+    InconsistentPsk,                 // psk and psk_id must both be given, and only in the PSK modes
+    KeyAgreement(KeyAgreementError), // an X25519 output was all zeros
+    SealError,                       // the AEAD refused the plaintext
+    OpenError,                       // the ciphertext or its associated data was not authentic
+    MessageLimitReached,             // the sequence number would wrap
+    ExportTooLong(usize),            // exports are limited to 255 * Nh bytes
+}
+
+impl fmt::Display for HpkeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HpkeError::InconsistentPsk => write!(f, "psk and psk_id do not match the mode"),
+            HpkeError::KeyAgreement(error) => write!(f, "key agreement failed: {}", error),
+            HpkeError::SealError => write!(f, "encryption failed"),
+            HpkeError::OpenError => write!(f, "decryption failed"),
+            HpkeError::MessageLimitReached => write!(f, "message limit reached"),
+            HpkeError::ExportTooLong(length) => write!(f, "export of {} bytes is too long", length),
+        }
+    }
+}
+
+impl std::error::Error for HpkeError {}
+
+impl From<KeyAgreementError> for HpkeError {
+    fn from(error: KeyAgreementError) -> HpkeError {
+        HpkeError::KeyAgreement(error)
+    }
+}
+
+/// The key derivation function of the key schedule and of secret export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HpkeKdf {
+    HkdfSha256,
+    HkdfSha384,
+    HkdfSha512,
+}
+
+impl HpkeKdf {
+    fn id(self) -> u16 {
+        match self {
+            HpkeKdf::HkdfSha256 => 0x0001,
+            HpkeKdf::HkdfSha384 => 0x0002,
+            HpkeKdf::HkdfSha512 => 0x0003,
+        }
+    }
+
+    // Nh, the hash output length
+    fn hash_len(self) -> usize {
+        match self {
+            HpkeKdf::HkdfSha256 => 32,
+            HpkeKdf::HkdfSha384 => 48,
+            HpkeKdf::HkdfSha512 => 64,
+        }
+    }
+
+    fn extract(self, salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+        match self {
+            HpkeKdf::HkdfSha256 => Hkdf::<Sha256>::extract(Some(salt), ikm).0.to_vec(),
+            HpkeKdf::HkdfSha384 => Hkdf::<Sha384>::extract(Some(salt), ikm).0.to_vec(),
+            HpkeKdf::HkdfSha512 => Hkdf::<Sha512>::extract(Some(salt), ikm).0.to_vec(),
+        }
+    }
+
+    // The caller bounds `length` by 255 * Nh, the most HKDF-Expand can produce
+    fn expand(self, prk: &[u8], info: &[&[u8]], length: usize) -> Vec<u8> {
+        let mut okm = vec![0u8; length];
+        let result = match self {
+            HpkeKdf::HkdfSha256 => Hkdf::<Sha256>::from_prk(prk).expect("prk is Nh bytes").expand_multi_info(info, &mut okm),
+            HpkeKdf::HkdfSha384 => Hkdf::<Sha384>::from_prk(prk).expect("prk is Nh bytes").expand_multi_info(info, &mut okm),
+            HpkeKdf::HkdfSha512 => Hkdf::<Sha512>::from_prk(prk).expect("prk is Nh bytes").expand_multi_info(info, &mut okm),
+        };
+        result.expect("length is at most 255 * Nh");
+        okm
+    }
+}
+
+/// The AEAD that seals the messages of a context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HpkeAead {
+    Aes128Gcm,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl HpkeAead {
+    fn id(self) -> u16 {
+        match self {
+            HpkeAead::Aes128Gcm => 0x0001,
+            HpkeAead::Aes256Gcm => 0x0002,
+            HpkeAead::ChaCha20Poly1305 => 0x0003,
+        }
+    }
+
+    // Nk, the key length
+    fn key_len(self) -> usize {
+        match self {
+            HpkeAead::Aes128Gcm => 16,
+            HpkeAead::Aes256Gcm | HpkeAead::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    fn seal(self, key: &[u8], nonce: &[u8; NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = GenericArray::from_slice(nonce);
+        let payload = Payload { msg: plaintext, aad };
+        let result = match self {
+            HpkeAead::Aes128Gcm => Aes128Gcm::new(GenericArray::from_slice(key)).encrypt(nonce, payload),
+            HpkeAead::Aes256Gcm => Aes256Gcm::new(GenericArray::from_slice(key)).encrypt(nonce, payload),
+            HpkeAead::ChaCha20Poly1305 => ChaCha20Poly1305::new(GenericArray::from_slice(key)).encrypt(nonce, payload),
+        };
+        result.map_err(|_| HpkeError::SealError)
+    }
+
+    fn open(self, key: &[u8], nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = GenericArray::from_slice(nonce);
+        let payload = Payload { msg: ciphertext, aad };
+        let result = match self {
+            HpkeAead::Aes128Gcm => Aes128Gcm::new(GenericArray::from_slice(key)).decrypt(nonce, payload),
+            HpkeAead::Aes256Gcm => Aes256Gcm::new(GenericArray::from_slice(key)).decrypt(nonce, payload),
+            HpkeAead::ChaCha20Poly1305 => ChaCha20Poly1305::new(GenericArray::from_slice(key)).decrypt(nonce, payload),
+        };
+        result.map_err(|_| HpkeError::OpenError)
+    }
+}
+
+/// A ciphersuite: the KEM is always DHKEM(X25519, HKDF-SHA256).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HpkeSuite {
+    pub kdf: HpkeKdf,
+    pub aead: HpkeAead,
+}
+
+impl HpkeSuite {
+    pub fn new(kdf: HpkeKdf, aead: HpkeAead) -> HpkeSuite {
+        HpkeSuite { kdf, aead }
+    }
+
+    // "HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2)
+    fn id(self) -> Vec<u8> {
+        let mut result = b"HPKE".to_vec();
+        result.extend_from_slice(&KEM_ID.to_be_bytes());
+        result.extend_from_slice(&self.kdf.id().to_be_bytes());
+        result.extend_from_slice(&self.aead.id().to_be_bytes());
+        result
+    }
+}
+
+// LabeledExtract and LabeledExpand of section 4, under the given suite_id
+fn labeled_extract(kdf: HpkeKdf, suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let mut labeled_ikm = [b"HPKE-v1", suite_id, label, ikm].concat();
+    let result = kdf.extract(salt, &labeled_ikm);
+    labeled_ikm.zeroize();
+    result
+}
+
+fn labeled_expand(kdf: HpkeKdf, suite_id: &[u8], prk: &[u8], label: &[u8], info: &[u8], length: usize) -> Vec<u8> {
+    kdf.expand(prk, &[&(length as u16).to_be_bytes(), b"HPKE-v1", suite_id, label, info], length)
+}
+
+// "KEM" || I2OSP(kem_id, 2)
+fn kem_suite_id() -> Vec<u8> {
+    [b"KEM".as_ref(), &KEM_ID.to_be_bytes()].concat()
+}
+
+/// DeriveKeyPair of RFC 9180 section 7.1.3: the key pair determined by the input keying
+/// material, so that it can be regenerated from a seed. Any length is accepted, but `ikm` SHOULD
+/// carry at least 32 bytes (Nsk) of entropy.
+pub fn derive_key_pair(ikm: &[u8]) -> (StaticSecret, PublicKey) {
+    let suite_id = kem_suite_id();
+    let mut dkp_prk = labeled_extract(HpkeKdf::HkdfSha256, &suite_id, b"", b"dkp_prk", ikm);
+    let mut sk = [0u8; 32];
+    let mut expanded = labeled_expand(HpkeKdf::HkdfSha256, &suite_id, &dkp_prk, b"sk", b"", 32);
+    sk.copy_from_slice(&expanded);
+    let secret = StaticSecret::from(sk);
+    dkp_prk.zeroize();
+    expanded.zeroize();
+    sk.zeroize();
+    let public = PublicKey::from(&secret);
+    (secret, public)
+}
+
+// ExtractAndExpand of section 4.1, turning the concatenated DH outputs into the shared secret
+fn extract_and_expand(dh: &[u8], kem_context: &[u8]) -> Vec<u8> {
+    let suite_id = kem_suite_id();
+    let mut eae_prk = labeled_extract(HpkeKdf::HkdfSha256, &suite_id, b"", b"eae_prk", dh);
+    let shared_secret = labeled_expand(HpkeKdf::HkdfSha256, &suite_id, &eae_prk, b"shared_secret", kem_context, 32);
+    eae_prk.zeroize();
+    shared_secret
+}
+
+// Encap, or AuthEncap when a sender key is given: returns (shared_secret, enc)
+fn encap(pk_r: &PublicKey, sk_s: Option<&StaticSecret>, sk_e: &StaticSecret) -> Result<(Vec<u8>, [u8; 32]), HpkeError> {
+    let enc = PublicKey::from(sk_e).to_bytes();
+    let mut dh = sk_e.diffie_hellman_checked(pk_r)?.as_bytes().to_vec();
+    let mut kem_context = [enc, pk_r.to_bytes()].concat();
+    if let Some(sk_s) = sk_s {
+        dh.extend_from_slice(sk_s.diffie_hellman_checked(pk_r)?.as_bytes());
+        kem_context.extend_from_slice(PublicKey::from(sk_s).as_bytes());
+    }
+    let shared_secret = extract_and_expand(&dh, &kem_context);
+    dh.zeroize();
+    Ok((shared_secret, enc))
+}
+
+// Decap, or AuthDecap when the sender's public key is given
+fn decap(enc: &[u8; 32], sk_r: &StaticSecret, pk_s: Option<&PublicKey>) -> Result<Vec<u8>, HpkeError> {
+    let pk_e = PublicKey::from(*enc);
+    let mut dh = sk_r.diffie_hellman_checked(&pk_e)?.as_bytes().to_vec();
+    let mut kem_context = [*enc, PublicKey::from(sk_r).to_bytes()].concat();
+    if let Some(pk_s) = pk_s {
+        dh.extend_from_slice(sk_r.diffie_hellman_checked(pk_s)?.as_bytes());
+        kem_context.extend_from_slice(pk_s.as_bytes());
+    }
+    let shared_secret = extract_and_expand(&dh, &kem_context);
+    dh.zeroize();
+    Ok(shared_secret)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Mode {
+    Base = 0,
+    Psk = 1,
+    Auth = 2,
+    AuthPsk = 3,
+}
+
+// KeySchedule of section 5.1, including VerifyPSKInputs
+fn key_schedule(suite: HpkeSuite, mode: Mode, shared_secret: &[u8], info: &[u8], psk: &[u8], psk_id: &[u8]) -> Result<Context, HpkeError> {
+    let uses_psk = mode == Mode::Psk || mode == Mode::AuthPsk;
+    if psk.is_empty() != psk_id.is_empty() || psk.is_empty() == uses_psk {
+        return Err(HpkeError::InconsistentPsk);
+    }
+    let suite_id = suite.id();
+    let kdf = suite.kdf;
+    let psk_id_hash = labeled_extract(kdf, &suite_id, b"", b"psk_id_hash", psk_id);
+    let info_hash = labeled_extract(kdf, &suite_id, b"", b"info_hash", info);
+    let key_schedule_context = [&[mode as u8], &psk_id_hash[..], &info_hash[..]].concat();
+    let mut secret = labeled_extract(kdf, &suite_id, shared_secret, b"secret", psk);
+    let key = labeled_expand(kdf, &suite_id, &secret, b"key", &key_schedule_context, suite.aead.key_len());
+    let mut base_nonce = [0u8; NONCE_LEN];
+    base_nonce.copy_from_slice(&labeled_expand(kdf, &suite_id, &secret, b"base_nonce", &key_schedule_context, NONCE_LEN));
+    let exporter_secret = labeled_expand(kdf, &suite_id, &secret, b"exp", &key_schedule_context, kdf.hash_len());
+    secret.zeroize();
+    Ok(Context { suite, key, base_nonce, seq: 0, exporter_secret })
+}
+
+// The state shared by both ends of a context
+struct Context {
+    suite: HpkeSuite,
+    key: Vec<u8>,
+    base_nonce: [u8; NONCE_LEN],
+    seq: u64,
+    exporter_secret: Vec<u8>,
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.base_nonce.zeroize();
+        self.exporter_secret.zeroize();
+    }
+}
+
+impl Context {
+    // ComputeNonce: base_nonce xor I2OSP(seq, Nn). A u64 sequence number runs out long before
+    // the 2^96 - 1 messages the RFC allows, so that is where the limit is enforced
+    fn compute_nonce(&self) -> Result<[u8; NONCE_LEN], HpkeError> {
+        if self.seq == u64::MAX {
+            return Err(HpkeError::MessageLimitReached);
+        }
+        let mut nonce = self.base_nonce;
+        for (byte, seq_byte) in nonce[NONCE_LEN - 8..].iter_mut().zip(self.seq.to_be_bytes().iter()) {
+            *byte ^= seq_byte;
+        }
+        Ok(nonce)
+    }
+
+    fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        if length > 255 * self.suite.kdf.hash_len() {
+            return Err(HpkeError::ExportTooLong(length));
+        }
+        Ok(labeled_expand(self.suite.kdf, &self.suite.id(), &self.exporter_secret, b"sec", exporter_context, length))
+    }
+}
+
+/// The sender's half of an HPKE context: seals messages in order for the matching receiver.
+pub struct HpkeSenderContext(Context);
+
+impl ZeroizeOnDrop for HpkeSenderContext {}
+
+impl HpkeSenderContext {
+    /// Encrypts the next message, authenticating `aad` along with it.
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.0.compute_nonce()?;
+        let ciphertext = self.0.suite.aead.seal(&self.0.key, &nonce, aad, plaintext)?;
+        self.0.seq += 1;
+        Ok(ciphertext)
+    }
+
+    /// A secret of `length` bytes bound to this context and `exporter_context`, as in section 5.3.
+    pub fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+/// The receiver's half of an HPKE context: opens messages in the order they were sealed.
+pub struct HpkeReceiverContext(Context);
+
+impl ZeroizeOnDrop for HpkeReceiverContext {}
+
+impl HpkeReceiverContext {
+    /// Decrypts the next message. A failure leaves the sequence number alone, so a forgery
+    /// does not desynchronize the context.
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.0.compute_nonce()?;
+        let plaintext = self.0.suite.aead.open(&self.0.key, &nonce, aad, ciphertext)?;
+        self.0.seq += 1;
+        Ok(plaintext)
+    }
+
+    /// The same secret the sender exports for `exporter_context`.
+    pub fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+// Encapsulates to pk_r with the given ephemeral key and runs the key schedule; `psk` holds
+// (psk, psk_id) and `sk_s` the sender key of the authenticated modes
+pub(crate) fn setup_sender(
+    suite: HpkeSuite, mode: Mode, pk_r: &PublicKey, info: &[u8], psk: (&[u8], &[u8]), sk_s: Option<&StaticSecret>, sk_e: &StaticSecret,
+) -> Result<([u8; 32], HpkeSenderContext), HpkeError> {
+    let (mut shared_secret, enc) = encap(pk_r, sk_s, sk_e)?;
+    let context = key_schedule(suite, mode, &shared_secret, info, psk.0, psk.1);
+    shared_secret.zeroize();
+    Ok((enc, HpkeSenderContext(context?)))
+}
+
+fn setup_receiver(
+    suite: HpkeSuite, mode: Mode, enc: &[u8; 32], sk_r: &StaticSecret, info: &[u8], psk: (&[u8], &[u8]), pk_s: Option<&PublicKey>,
+) -> Result<HpkeReceiverContext, HpkeError> {
+    let mut shared_secret = decap(enc, sk_r, pk_s)?;
+    let context = key_schedule(suite, mode, &shared_secret, info, psk.0, psk.1);
+    shared_secret.zeroize();
+    Ok(HpkeReceiverContext(context?))
+}
+
+/// SetupBaseS: encrypts to `pk_r` with a fresh ephemeral key, returning the encapsulated key
+/// `enc` that the receiver needs together with the sender's context.
+pub fn setup_base_s<T: RngCore + CryptoRng>(
+    suite: HpkeSuite, pk_r: &PublicKey, info: &[u8], csprng: &mut T,
+) -> Result<([u8; 32], HpkeSenderContext), HpkeError> {
+    setup_sender(suite, Mode::Base, pk_r, info, (b"", b""), None, &StaticSecret::random_from_rng(csprng))
+}
+
+/// SetupBaseR: the receiver's context for `enc`.
+pub fn setup_base_r(suite: HpkeSuite, enc: &[u8; 32], sk_r: &StaticSecret, info: &[u8]) -> Result<HpkeReceiverContext, HpkeError> {
+    setup_receiver(suite, Mode::Base, enc, sk_r, info, (b"", b""), None)
+}
+
+/// SetupPSKS: as `setup_base_s`, additionally keyed by a pre-shared key and its identifier,
+/// neither of which may be empty.
+pub fn setup_psk_s<T: RngCore + CryptoRng>(
+    suite: HpkeSuite, pk_r: &PublicKey, info: &[u8], psk: &[u8], psk_id: &[u8], csprng: &mut T,
+) -> Result<([u8; 32], HpkeSenderContext), HpkeError> {
+    setup_sender(suite, Mode::Psk, pk_r, info, (psk, psk_id), None, &StaticSecret::random_from_rng(csprng))
+}
+
+/// SetupPSKR: the receiver's context for `enc` under the same pre-shared key.
+pub fn setup_psk_r(
+    suite: HpkeSuite, enc: &[u8; 32], sk_r: &StaticSecret, info: &[u8], psk: &[u8], psk_id: &[u8],
+) -> Result<HpkeReceiverContext, HpkeError> {
+    setup_receiver(suite, Mode::Psk, enc, sk_r, info, (psk, psk_id), None)
+}
+
+/// SetupAuthS: as `setup_base_s`, additionally proving possession of the sender key `sk_s`.
+pub fn setup_auth_s<T: RngCore + CryptoRng>(
+    suite: HpkeSuite, pk_r: &PublicKey, info: &[u8], sk_s: &StaticSecret, csprng: &mut T,
+) -> Result<([u8; 32], HpkeSenderContext), HpkeError> {
+    setup_sender(suite, Mode::Auth, pk_r, info, (b"", b""), Some(sk_s), &StaticSecret::random_from_rng(csprng))
+}
+
+/// SetupAuthR: the receiver's context for `enc`, which opens only if the sender held the
+/// secret key behind `pk_s`.
+pub fn setup_auth_r(suite: HpkeSuite, enc: &[u8; 32], sk_r: &StaticSecret, info: &[u8], pk_s: &PublicKey) -> Result<HpkeReceiverContext, HpkeError> {
+    setup_receiver(suite, Mode::Auth, enc, sk_r, info, (b"", b""), Some(pk_s))
+}
+
+/// SetupAuthPSKS: both a pre-shared key and a sender key.
+pub fn setup_auth_psk_s<T: RngCore + CryptoRng>(
+    suite: HpkeSuite, pk_r: &PublicKey, info: &[u8], psk: &[u8], psk_id: &[u8], sk_s: &StaticSecret, csprng: &mut T,
+) -> Result<([u8; 32], HpkeSenderContext), HpkeError> {
+    setup_sender(suite, Mode::AuthPsk, pk_r, info, (psk, psk_id), Some(sk_s), &StaticSecret::random_from_rng(csprng))
+}
+
+/// SetupAuthPSKR: the receiver's context for `enc` under both the pre-shared key and `pk_s`.
+pub fn setup_auth_psk_r(
+    suite: HpkeSuite, enc: &[u8; 32], sk_r: &StaticSecret, info: &[u8], psk: &[u8], psk_id: &[u8], pk_s: &PublicKey,
+) -> Result<HpkeReceiverContext, HpkeError> {
+    setup_receiver(suite, Mode::AuthPsk, enc, sk_r, info, (psk, psk_id), Some(pk_s))
+}
@@ -0,0 +1,202 @@
+#![deny(clippy::all)]
+
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::arith::Fe25519;
+use crate::{x25519, x25519_base};
+
+// The u-coordinates of the points of order dividing 8: 0, 1, p - 1 and the two of order 8
+// (https://cr.yp.to/ecdh.html#validate). Every multiple of 8 sends them to u = 0
+const LOW_ORDER_U: [Fe25519; 5] = [
+    Fe25519::ZERO,
+    Fe25519::ONE,
+    Fe25519 { x3: 0x7FFF_FFFF_FFFF_FFFF, x2: 0xFFFF_FFFF_FFFF_FFFF, x1: 0xFFFF_FFFF_FFFF_FFFF, x0: 0xFFFF_FFFF_FFFF_FFEC },
+    Fe25519 { x3: 0x00B8_495F_1605_6286, x2: 0xFDB1_329C_EB8D_09DA, x1: 0x6AC4_9FF1_FAE3_5616, x0: 0xAEB8_413B_7C7A_EBE0 },
+    Fe25519 { x3: 0x5711_9FD0_DD4E_22D8, x2: 0x868E_1C58_C45C_4404, x1: 0x5BEF_839C_55B1_D0B1, x0: 0x248C_50A3_BC95_9C5F },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAgreementError {
+    NonContributory, // the shared secret is all zeros, as for any low-order public key
+}
+
+impl fmt::Display for KeyAgreementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyAgreementError::NonContributory => write!(f, "shared secret is all zeros"),
+        }
+    }
+}
+
+impl std::error::Error for KeyAgreementError {}
+
+// RFC 7748 section 6.1: the all-zero check, in constant time up to the final answer
+fn check_contributory(bytes: [u8; 32]) -> Result<SharedSecret, KeyAgreementError> {
+    let shared = SharedSecret { bytes };
+    if bool::from(shared.bytes.ct_eq(&[0u8; 32])) {
+        Err(KeyAgreementError::NonContributory)
+    } else {
+        Ok(shared)
+    }
+}
+
+// Secrets are kept as clamped byte strings; they are scalars, not field elements, so they never
+// pass through Fe25519::from_bytes (which would reduce them mod p)
+fn clamp(mut bytes: [u8; 32]) -> [u8; 32] {
+    bytes[0] &= 248;
+    bytes[31] &= 127;
+    bytes[31] |= 64;
+    bytes
+}
+
+/// A clamped X25519 secret scalar that can be used for any number of key agreements.
+#[derive(Clone)]
+pub struct StaticSecret {
+    bytes: [u8; 32],
+}
+
+impl Drop for StaticSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for StaticSecret {}
+
+impl StaticSecret {
+    pub fn random_from_rng<T: RngCore + CryptoRng>(csprng: &mut T) -> StaticSecret {
+        let mut bytes = [0u8; 32];
+        csprng.fill_bytes(&mut bytes);
+        let secret = StaticSecret::from(bytes);
+        bytes.zeroize();
+        secret
+    }
+
+    pub fn diffie_hellman(&self, their_public: &PublicKey) -> SharedSecret {
+        SharedSecret { bytes: x25519(self.bytes, their_public.bytes) }
+    }
+
+    /// As `diffie_hellman`, but fails when the result is all zeros, so that a peer cannot force
+    /// a shared secret that does not depend on this key.
+    pub fn diffie_hellman_checked(&self, their_public: &PublicKey) -> Result<SharedSecret, KeyAgreementError> {
+        check_contributory(x25519(self.bytes, their_public.bytes))
+    }
+
+    /// The clamped scalar, little-endian encoded.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+}
+
+impl Zeroize for StaticSecret {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl From<[u8; 32]> for StaticSecret {
+    fn from(bytes: [u8; 32]) -> StaticSecret {
+        StaticSecret { bytes: clamp(bytes) }
+    }
+}
+
+/// A clamped X25519 secret scalar that is consumed by its one and only key agreement.
+pub struct EphemeralSecret {
+    bytes: [u8; 32],
+}
+
+impl Drop for EphemeralSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for EphemeralSecret {}
+
+impl EphemeralSecret {
+    pub fn random_from_rng<T: RngCore + CryptoRng>(csprng: &mut T) -> EphemeralSecret {
+        let mut bytes = [0u8; 32];
+        csprng.fill_bytes(&mut bytes);
+        let secret = EphemeralSecret { bytes: clamp(bytes) };
+        bytes.zeroize();
+        secret
+    }
+
+    pub fn diffie_hellman(self, their_public: &PublicKey) -> SharedSecret {
+        SharedSecret { bytes: x25519(self.bytes, their_public.bytes) }
+    }
+
+    /// As `diffie_hellman`, but fails when the result is all zeros.
+    pub fn diffie_hellman_checked(self, their_public: &PublicKey) -> Result<SharedSecret, KeyAgreementError> {
+        check_contributory(x25519(self.bytes, their_public.bytes))
+    }
+}
+
+/// An X25519 public key: the little-endian encoded u-coordinate of a point.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PublicKey {
+    bytes: [u8; 32],
+}
+
+impl PublicKey {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Constant-time blocklist check: true when the u-coordinate, reduced mod p and with bit 255
+    /// ignored as X25519 does, is that of a low-order point. Lets a protocol reject such keys
+    /// before any secret is involved; `diffie_hellman_checked` catches them afterwards anyway.
+    pub fn is_low_order(&self) -> Choice {
+        let u = Fe25519::from_bytes(&self.bytes);
+        LOW_ORDER_U.iter().fold(Choice::from(0), |found, low_order| found | u.ct_eq(low_order))
+    }
+}
+
+impl From<[u8; 32]> for PublicKey {
+    fn from(bytes: [u8; 32]) -> PublicKey {
+        PublicKey { bytes }
+    }
+}
+
+impl<'a> From<&'a StaticSecret> for PublicKey {
+    fn from(secret: &'a StaticSecret) -> PublicKey {
+        PublicKey { bytes: x25519_base(secret.bytes) }
+    }
+}
+
+impl<'a> From<&'a EphemeralSecret> for PublicKey {
+    fn from(secret: &'a EphemeralSecret) -> PublicKey {
+        PublicKey { bytes: x25519_base(secret.bytes) }
+    }
+}
+
+/// The result of an X25519 key agreement, to be fed to a key derivation function.
+pub struct SharedSecret {
+    bytes: [u8; 32],
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SharedSecret {}
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+}
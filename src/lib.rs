@@ -0,0 +1,114 @@
+#![deny(clippy::all)]
+
+// See: https://tools.ietf.org/html/rfc7748
+// cargo test --color=always --package curve25519 --lib -- --nocapture
+
+#[cfg(test)]
+#[macro_use]
+extern crate lazy_static;
+
+use zeroize::Zeroize;
+
+mod arith;
+mod arith448;
+mod basepoint_table;
+mod crypto_box;
+mod ed25519;
+mod ed448;
+mod edwards;
+mod edwards448;
+mod elligator;
+mod hash_to_curve;
+mod hpke;
+mod keys;
+mod msm;
+mod ristretto;
+mod scalar;
+mod scalar448;
+mod support;
+#[cfg(test)]
+mod tests;
+
+pub use crate::arith::{get_k, get_u, try_get_k, try_get_u, Fe25519};
+pub use crate::arith448::Fe448;
+pub use crate::crypto_box::{
+    crypto_box_beforenm, crypto_box_easy, crypto_box_easy_afternm, crypto_box_keypair, crypto_box_open_easy, crypto_box_open_easy_afternm,
+    crypto_box_seal, crypto_box_seal_open, CryptoBoxError, CRYPTO_BOX_MACBYTES, CRYPTO_BOX_NONCEBYTES, CRYPTO_BOX_SEALBYTES,
+};
+pub use crate::ed25519::{ed25519ph_prehash, verify_batch, Signature, SignatureError, SigningKey, VerifyingKey};
+pub use crate::ed448::{ed448ph_prehash, Ed448Signature, Ed448SigningKey, Ed448VerifyingKey};
+pub use crate::edwards::{EdwardsPoint, ED25519_BASEPOINT};
+pub use crate::edwards448::{Edwards448Point, ED448_BASEPOINT};
+pub use crate::elligator::{elligator2_inverse, elligator2_keypair, elligator2_map, elligator2_public_key, elligator2_representative};
+pub use crate::hash_to_curve::{
+    encode_to_curve25519, encode_to_edwards25519, expand_message_xmd, hash_to_curve25519, hash_to_edwards25519, hash_to_field,
+    HashToCurveError,
+};
+pub use crate::hpke::{
+    derive_key_pair, setup_auth_psk_r, setup_auth_psk_s, setup_auth_r, setup_auth_s, setup_base_r, setup_base_s, setup_psk_r, setup_psk_s, HpkeAead,
+    HpkeError, HpkeKdf, HpkeReceiverContext, HpkeSenderContext, HpkeSuite,
+};
+pub use crate::keys::{EphemeralSecret, KeyAgreementError, PublicKey, SharedSecret, StaticSecret};
+pub use crate::ristretto::{RistrettoPoint, RISTRETTO_BASEPOINT};
+pub use crate::scalar::Scalar;
+pub use crate::scalar448::Scalar448;
+pub use crate::support::ParseError;
+
+/// The u-coordinate of the Curve25519 base point (u = 9), little-endian encoded.
+pub const X25519_BASEPOINT: [u8; 32] = [9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// The u-coordinate of the Curve448 base point (u = 5), little-endian encoded.
+pub const X448_BASEPOINT: [u8; 56] = [
+    5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0,
+];
+
+/// The X25519 function of RFC 7748 section 5: clamps `scalar`, decodes `u` and runs the
+/// Montgomery ladder, returning the little-endian encoded u-coordinate of the result.
+pub fn x25519(mut scalar: [u8; 32], u: [u8; 32]) -> [u8; 32] {
+    let mut k = arith::decode_scalar(&scalar);
+    let u = Fe25519::from_bytes(&u);
+    let mut result = Fe25519::default();
+    arith::mul(&mut result, &k, u);
+    let output = result.to_bytes();
+    scalar.zeroize();
+    k.zeroize();
+    result.zeroize();
+    output
+}
+
+/// `x25519(scalar, X25519_BASEPOINT)`, computed as a fixed-base multiplication on the equivalent
+/// Edwards curve with a precomputed table and mapped back to u; much faster than the ladder.
+pub fn x25519_base(mut scalar: [u8; 32]) -> [u8; 32] {
+    let point = EdwardsPoint::mul_base_clamped(scalar);
+    scalar.zeroize();
+    point.to_montgomery().to_bytes()
+}
+
+/// X25519 over many (scalar, u) pairs at once. Each output equals `x25519(scalars[i], u[i])`,
+/// but the ladders share a single field inversion, which dominates for large batches.
+pub fn x25519_batch(scalars: &[[u8; 32]], u: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    assert_eq!(scalars.len(), u.len(), "one u-coordinate is needed per scalar");
+    let mut k: Vec<Fe25519> = scalars.iter().map(arith::decode_scalar).collect();
+    let u: Vec<Fe25519> = u.iter().map(Fe25519::from_bytes).collect();
+    let mut results = vec![Fe25519::default(); k.len()];
+    arith::mul_batch(&mut results, &k, &u);
+    let output = results.iter().map(Fe25519::to_bytes).collect();
+    k.zeroize();
+    results.zeroize();
+    output
+}
+
+/// The X448 function of RFC 7748 section 5: clamps `scalar` with decodeScalar448, decodes `u`
+/// and runs the 448-bit Montgomery ladder, returning the encoded u-coordinate of the result.
+pub fn x448(mut scalar: [u8; 56], u: [u8; 56]) -> [u8; 56] {
+    let mut k = arith448::decode_scalar448(&scalar);
+    let u = Fe448::from_bytes(&u);
+    let mut result = Fe448::default();
+    arith448::mul448(&mut result, &k, &u);
+    let output = result.to_bytes();
+    scalar.zeroize();
+    k.zeroize();
+    result.zeroize();
+    output
+}
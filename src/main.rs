@@ -1,16 +1,13 @@
 #![deny(clippy::all)]
 
 // See: https://tools.ietf.org/html/rfc7748
-// cargo test --color=always --package curve25519 --bin curve25519 -- --nocapture
+// The arithmetic and its tests live in the library; this binary is just a consumer.
 
-#[macro_use]
-extern crate lazy_static;
+use curve25519::{x25519, X25519_BASEPOINT};
 
-mod arith;
-mod support;
-mod tests;
-
-// TODO: The actual tests are in tests.rs - not here!!!
 fn main() {
     println!("Starting...");
+    let result = x25519(X25519_BASEPOINT, X25519_BASEPOINT);
+    let hex: Vec<String> = result.iter().map(|b| format!("{:02x}", b)).collect();
+    println!("X25519(9, 9) = {}", hex.concat());
 }
@@ -0,0 +1,137 @@
+#![deny(clippy::all)]
+
+// Multiscalar multiplication sum [a_i] P_i over edwards25519. In variable time: Straus's method
+// with width-5 NAFs for short sums, and Pippenger's bucket method, whose cost per term falls as
+// the window grows with n, for long ones. In constant time: Straus with signed radix-16 digits
+// and a lookup that reads every table entry.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::edwards::EdwardsPoint;
+use crate::scalar::{as_radix_16, Scalar};
+
+// Below this many terms Straus beats Pippenger
+const PIPPENGER_THRESHOLD: usize = 190;
+
+impl EdwardsPoint {
+    /// sum [scalars[i]] points[i]. Branches and indexes memory on the scalars, so only for public
+    /// inputs such as the signatures in `verify_batch`.
+    pub fn vartime_multiscalar_mul(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+        assert_eq!(scalars.len(), points.len(), "one point is needed per scalar");
+        if scalars.len() < PIPPENGER_THRESHOLD {
+            straus_vartime(scalars, points)
+        } else {
+            pippenger_vartime(scalars, points)
+        }
+    }
+
+    /// sum [scalars[i]] points[i] in constant time, for secret scalars; the running time depends
+    /// only on the number of terms.
+    pub fn multiscalar_mul(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+        assert_eq!(scalars.len(), points.len(), "one point is needed per scalar");
+        // tables[i][j] = [j + 1] points[i]
+        let tables: Vec<[EdwardsPoint; 8]> = points
+            .iter()
+            .map(|point| {
+                let mut table = [*point; 8];
+                for j in 1..8 {
+                    table[j] = table[j - 1] + point;
+                }
+                table
+            })
+            .collect();
+        let mut digits: Vec<[i8; 64]> = scalars.iter().map(|scalar| as_radix_16(&scalar.to_bytes())).collect();
+        let mut result = EdwardsPoint::identity();
+        for i in (0..64).rev() {
+            result = result.mul_by_pow_2(4);
+            for (table, digits) in tables.iter().zip(digits.iter()) {
+                result += &select(table, digits[i]);
+            }
+        }
+        digits.zeroize();
+        result
+    }
+}
+
+// [digit] P from the table of [1]P .. [8]P, for a digit in [-8, 8], reading every entry
+fn select(table: &[EdwardsPoint; 8], digit: i8) -> EdwardsPoint {
+    let sign_mask = digit >> 7;
+    let magnitude = ((digit + sign_mask) ^ sign_mask) as u8;
+    let mut result = EdwardsPoint::identity();
+    for (j, entry) in table.iter().enumerate() {
+        result.conditional_assign(entry, magnitude.ct_eq(&(j as u8 + 1)));
+    }
+    let negative = Choice::from((sign_mask & 1) as u8);
+    result.x.conditional_negate(negative);
+    result.t.conditional_negate(negative);
+    result
+}
+
+// One run of doublings shared by all terms, adding or subtracting the odd multiples [1]P, [3]P
+// .. [15]P wherever a NAF digit is nonzero
+fn straus_vartime(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+    let nafs: Vec<[i8; 256]> = scalars.iter().map(|scalar| scalar.non_adjacent_form(5)).collect();
+    let tables: Vec<[EdwardsPoint; 8]> = points
+        .iter()
+        .map(|point| {
+            let double = point.double();
+            let mut table = [*point; 8];
+            for j in 1..8 {
+                table[j] = table[j - 1] + double;
+            }
+            table
+        })
+        .collect();
+    // Leading positions where every NAF is zero would only double the identity
+    let top = (0..256).rev().find(|&i| nafs.iter().any(|naf| naf[i] != 0));
+    let mut result = EdwardsPoint::identity();
+    for i in (0..=top.unwrap_or(0)).rev() {
+        result = result.double();
+        for (naf, table) in nafs.iter().zip(tables.iter()) {
+            let digit = naf[i];
+            if digit > 0 {
+                result += &table[(digit / 2) as usize];
+            } else if digit < 0 {
+                result -= &table[(-digit / 2) as usize];
+            }
+        }
+    }
+    result
+}
+
+// Splits each scalar into signed radix-2^w digits and, per digit position from the top, sorts
+// the points into 2^(w - 1) buckets by |digit|, then sums the buckets with their weights through
+// a running sum: sum (j + 1) B_j = B_top + (B_top + B_top-1) + ...
+fn pippenger_vartime(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+    let w = match scalars.len() {
+        0..=499 => 6,
+        500..=799 => 7,
+        _ => 8,
+    };
+    let all_digits: Vec<Vec<i16>> = scalars.iter().map(|scalar| scalar.as_radix_2w(w)).collect();
+    let mut buckets = vec![EdwardsPoint::identity(); 1 << (w - 1)];
+    let mut result = EdwardsPoint::identity();
+    for i in (0..all_digits[0].len()).rev() {
+        result = result.mul_by_pow_2(w as u32);
+        for bucket in buckets.iter_mut() {
+            *bucket = EdwardsPoint::identity();
+        }
+        for (digits, point) in all_digits.iter().zip(points) {
+            let digit = digits[i];
+            if digit > 0 {
+                buckets[(digit - 1) as usize] += point;
+            } else if digit < 0 {
+                buckets[(-digit - 1) as usize] -= point;
+            }
+        }
+        let mut running_sum = EdwardsPoint::identity();
+        let mut weighted_sum = EdwardsPoint::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum += bucket;
+            weighted_sum += &running_sum;
+        }
+        result += &weighted_sum;
+    }
+    result
+}
@@ -0,0 +1,213 @@
+#![deny(clippy::all)]
+
+// See: https://www.rfc-editor.org/rfc/rfc9496 (section 4)
+// ristretto255: the prime-order quotient of edwards25519 by its 4-torsion, so each element is a
+// coset of four Edwards points and the cofactor never leaks into higher-level protocols
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use sha2::{Digest, Sha512};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::arith::{Fe25519, SQRT_M1};
+use crate::edwards::{EdwardsPoint, ED25519_BASEPOINT, EDWARDS_D};
+use crate::scalar::Scalar;
+
+// sqrt(a * d - 1), 1 / sqrt(a - d), 1 - d^2 and (d - 1)^2 with a = -1; the roots are the ones
+// RFC 9496 section 4.1 lists, and the first of them is odd
+const SQRT_AD_MINUS_ONE: Fe25519 = Fe25519 { x3: 0x3769_31BF_2B83_48AC, x2: 0x0F3C_FCC9_31F5_D1FD, x1: 0xAF9D_8E0C_1B78_54BD, x0: 0x7E97_F6A0_497B_2E1B };
+const INVSQRT_A_MINUS_D: Fe25519 = Fe25519 { x3: 0x786C_8905_CFAF_FCA2, x2: 0x16C2_7B91_FE01_D840, x1: 0x9D2F_1617_5A41_72BE, x0: 0x99C8_FDAA_805D_40EA };
+const ONE_MINUS_D_SQ: Fe25519 = Fe25519 { x3: 0x0290_72A8_B2B3_E0D7, x2: 0x9994_ABDD_BE70_DFE4, x1: 0x2C81_A138_CD5E_350F, x0: 0xE27C_09C1_945F_C176 };
+const D_MINUS_ONE_SQ: Fe25519 = Fe25519 { x3: 0x5968_B37A_F66C_2241, x2: 0x4CDC_D32F_529B_4EEB, x1: 0xD29E_4A2C_B01E_1999, x0: 0x31AD_5AAA_44ED_4D20 };
+
+/// The ristretto255 generator, represented by the Ed25519 base point.
+pub const RISTRETTO_BASEPOINT: RistrettoPoint = RistrettoPoint(ED25519_BASEPOINT);
+
+// CT_ABS: the non-negative one of x and -x
+fn ct_abs(x: &Fe25519) -> Fe25519 {
+    let mut result = *x;
+    result.conditional_negate(x.is_negative());
+    result
+}
+
+/// An element of the ristretto255 group, held as any one of the Edwards points in its coset.
+#[derive(Clone, Copy, Debug)]
+pub struct RistrettoPoint(pub(crate) EdwardsPoint);
+
+impl RistrettoPoint {
+    pub fn identity() -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::identity())
+    }
+
+    /// RFC 9496 section 4.3.1; rejects non-canonical and negative s, and anything that is not
+    /// the encoding of a group element.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<RistrettoPoint> {
+        let s: Option<Fe25519> = Fe25519::try_from_canonical_bytes(bytes).into();
+        let s = s?;
+        if bool::from(s.is_negative()) {
+            return None;
+        }
+        let ss = s.square();
+        let u1 = Fe25519::ONE - ss;
+        let u2 = Fe25519::ONE + ss;
+        let u2_sqr = u2.square();
+        let v = -(EDWARDS_D * u1.square()) - u2_sqr;
+        let (was_square, invsqrt) = Fe25519::sqrt_ratio(&Fe25519::ONE, &(v * u2_sqr));
+        let den_x = invsqrt * u2;
+        let den_y = invsqrt * den_x * v;
+        let x = ct_abs(&((s + s) * den_x));
+        let y = u1 * den_y;
+        let t = x * y;
+        if !bool::from(was_square) || bool::from(t.is_negative()) || bool::from(y.ct_eq(&Fe25519::ZERO)) {
+            return None;
+        }
+        Some(RistrettoPoint(EdwardsPoint { x, y, z: Fe25519::ONE, t }))
+    }
+
+    /// RFC 9496 section 4.3.2: the same 32 bytes for every representative of the coset.
+    pub fn compress(&self) -> [u8; 32] {
+        let EdwardsPoint { x: x0, y: y0, z: z0, t: t0 } = self.0;
+        let u1 = (z0 + y0) * (z0 - y0);
+        let u2 = x0 * y0;
+        let (_, invsqrt) = Fe25519::sqrt_ratio(&Fe25519::ONE, &(u1 * u2.square()));
+        let den1 = invsqrt * u1;
+        let den2 = invsqrt * u2;
+        let z_inv = den1 * den2 * t0;
+        let ix0 = x0 * SQRT_M1;
+        let iy0 = y0 * SQRT_M1;
+        let enchanted_denominator = den1 * INVSQRT_A_MINUS_D;
+        let rotate = (t0 * z_inv).is_negative();
+        let x = Fe25519::conditional_select(&x0, &iy0, rotate);
+        let mut y = Fe25519::conditional_select(&y0, &ix0, rotate);
+        let den_inv = Fe25519::conditional_select(&den2, &enchanted_denominator, rotate);
+        y.conditional_negate((x * z_inv).is_negative());
+        ct_abs(&(den_inv * (z0 - y))).to_bytes()
+    }
+
+    /// The element derivation of RFC 9496 section 4.3.4: maps each half of 64 uniformly random
+    /// bytes to the group and adds the results, giving a uniformly distributed element whose
+    /// discrete log is unknown.
+    pub fn from_uniform_bytes(bytes: &[u8; 64]) -> RistrettoPoint {
+        let mut half = [0u8; 32];
+        half.copy_from_slice(&bytes[..32]);
+        let p1 = RistrettoPoint::map(&Fe25519::from_bytes(&half));
+        half.copy_from_slice(&bytes[32..]);
+        let p2 = RistrettoPoint::map(&Fe25519::from_bytes(&half));
+        half.zeroize();
+        p1 + p2
+    }
+
+    /// Hashes arbitrary input to the group: SHA-512 of `input` fed to `from_uniform_bytes`.
+    pub fn hash_from_bytes(input: &[u8]) -> RistrettoPoint {
+        let digest: [u8; 64] = Sha512::digest(input).into();
+        RistrettoPoint::from_uniform_bytes(&digest)
+    }
+
+    // MAP(t) of RFC 9496 section 4.3.4, an Elligator variant landing in the group
+    fn map(t: &Fe25519) -> RistrettoPoint {
+        let r = SQRT_M1 * t.square();
+        let u = (r + Fe25519::ONE) * ONE_MINUS_D_SQ;
+        let v = (-Fe25519::ONE - r * EDWARDS_D) * (r + EDWARDS_D);
+        let (was_square, s) = Fe25519::sqrt_ratio(&u, &v);
+        let s_prime = -ct_abs(&(s * t));
+        let s = Fe25519::conditional_select(&s_prime, &s, was_square);
+        let c = Fe25519::conditional_select(&r, &-Fe25519::ONE, was_square);
+        let n = c * (r - Fe25519::ONE) * D_MINUS_ONE_SQ - v;
+        let w0 = (s + s) * v;
+        let w1 = n * SQRT_AD_MINUS_ONE;
+        let w2 = Fe25519::ONE - s.square();
+        let w3 = Fe25519::ONE + s.square();
+        RistrettoPoint(EdwardsPoint { x: w0 * w3, y: w2 * w1, z: w1 * w3, t: w0 * w2 })
+    }
+}
+
+impl ConstantTimeEq for RistrettoPoint {
+    // Section 4.3.3: equal cosets have x1 * y2 = y1 * x2 or y1 * y2 = x1 * x2
+    fn ct_eq(&self, other: &RistrettoPoint) -> Choice {
+        let (p, q) = (&self.0, &other.0);
+        (p.x * q.y).ct_eq(&(p.y * q.x)) | (p.y * q.y).ct_eq(&(p.x * q.x))
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &RistrettoPoint) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for RistrettoPoint {}
+
+impl ConditionallySelectable for RistrettoPoint {
+    fn conditional_select(a: &RistrettoPoint, b: &RistrettoPoint, choice: Choice) -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Add<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn add(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.add_extended(&other.0))
+    }
+}
+
+impl Add<RistrettoPoint> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn add(self, other: RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.add_extended(&other.0))
+    }
+}
+
+impl AddAssign<&RistrettoPoint> for RistrettoPoint {
+    fn add_assign(&mut self, other: &RistrettoPoint) {
+        self.0 = self.0.add_extended(&other.0);
+    }
+}
+
+impl Neg for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(-self.0)
+    }
+}
+
+impl Neg for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(-self.0)
+    }
+}
+
+impl Sub<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn sub(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.add_extended(&-other.0))
+    }
+}
+
+impl Sub<RistrettoPoint> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn sub(self, other: RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.add_extended(&-other.0))
+    }
+}
+
+impl SubAssign<&RistrettoPoint> for RistrettoPoint {
+    fn sub_assign(&mut self, other: &RistrettoPoint) {
+        self.0 = self.0.add_extended(&-other.0);
+    }
+}
+
+impl Mul<&Scalar> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn mul(self, scalar: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(self.0 * scalar)
+    }
+}
+
+impl Mul<&Scalar> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn mul(self, scalar: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(self.0 * scalar)
+    }
+}
@@ -0,0 +1,332 @@
+#![deny(clippy::all)]
+
+// Integers mod l = 2^252 + 27742317777372353535851937790883648493, the prime order of the
+// Ed25519 base point (RFC 8032 section 5.1). Five 52-bit limbs, Montgomery multiplication with
+// R = 2^260; every routine is branch-free and leaves its result fully reduced.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
+
+const MASK52: u64 = (1 << 52) - 1;
+
+// l, least significant limb first
+const L: Scalar = Scalar { limbs: [0x0002_631A_5CF5_D3ED, 0x000D_EA2F_79CD_6581, 0x0000_0000_0014_DEF9, 0, 0x0000_1000_0000_0000] };
+
+// -1 / l mod 2^52
+const LFACTOR: u64 = 0x0005_1DA3_1254_7E1B;
+
+// R mod l and R^2 mod l
+const R: Scalar = Scalar { limbs: [0x000F_48BD_6721_E6ED, 0x0003_BAB5_AC67_E45A, 0x000F_FFFF_EB35_E51B, 0x000F_FFFF_FFFF_FFFF, 0x0000_0FFF_FFFF_FFFF] };
+const RR: Scalar = Scalar { limbs: [0x0009_D265_E952_D13B, 0x000D_63C7_15BE_A69F, 0x0005_BE65_CB68_7604, 0x0003_DCEE_C73D_217F, 0x0000_0941_1B7C_309A] };
+
+/// An integer mod l, the order of the Ed25519 base point.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scalar {
+    // 52+52+52+52+48=256; limbs[0] is LSB
+    pub(crate) limbs: [u64; 5],
+}
+
+impl Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Scalar) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Scalar) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Scalar {}
+
+impl ConditionallySelectable for Scalar {
+    fn conditional_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+        let mut result = Scalar::default();
+        for i in 0..5 {
+            result.limbs[i] = u64::conditional_select(&a.limbs[i], &b.limbs[i], choice);
+        }
+        result
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Scalar {
+        Scalar { limbs: [value & MASK52, value >> 52, 0, 0, 0] }
+    }
+}
+
+impl Scalar {
+    pub const ZERO: Scalar = Scalar { limbs: [0, 0, 0, 0, 0] };
+    pub const ONE: Scalar = Scalar { limbs: [1, 0, 0, 0, 0] };
+
+    /// Reduces a 256-bit little-endian integer mod l.
+    pub fn from_bytes_mod_order(bytes: &[u8; 32]) -> Scalar {
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(bytes);
+        let result = Scalar::from_bytes_mod_order_wide(&wide);
+        wide.zeroize();
+        result
+    }
+
+    /// Reduces a 512-bit little-endian integer mod l, as RFC 8032 does with SHA-512 outputs.
+    pub fn from_bytes_mod_order_wide(bytes: &[u8; 64]) -> Scalar {
+        let mut words = [0u64; 8];
+        for (i, word) in words.iter_mut().enumerate() {
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[8 * i..8 * i + 8]);
+            *word = u64::from_le_bytes(chunk);
+        }
+        // Split into a low and a high 260-bit half: value = lo + hi * 2^260
+        let lo = Scalar {
+            limbs: [
+                words[0] & MASK52,
+                ((words[0] >> 52) | (words[1] << 12)) & MASK52,
+                ((words[1] >> 40) | (words[2] << 24)) & MASK52,
+                ((words[2] >> 28) | (words[3] << 36)) & MASK52,
+                ((words[3] >> 16) | (words[4] << 48)) & MASK52,
+            ],
+        };
+        let hi = Scalar {
+            limbs: [
+                (words[4] >> 4) & MASK52,
+                ((words[4] >> 56) | (words[5] << 8)) & MASK52,
+                ((words[5] >> 44) | (words[6] << 20)) & MASK52,
+                ((words[6] >> 32) | (words[7] << 32)) & MASK52,
+                words[7] >> 20,
+            ],
+        };
+        // lo * R / R = lo and hi * R^2 / R = hi * R, each reduced
+        let mut result = Scalar::default();
+        let lo = sc_montgomery_mul(&lo, &R);
+        let hi = sc_montgomery_mul(&hi, &RR);
+        sc_add(&mut result, &lo, &hi);
+        words.zeroize();
+        result
+    }
+
+    /// Accepts only the encodings of 0..l-1, as RFC 8032 requires of the S half of a signature.
+    pub fn try_from_canonical_bytes(bytes: &[u8; 32]) -> CtOption<Scalar> {
+        let candidate = Scalar::from_bytes_mod_order(bytes);
+        CtOption::new(candidate, Scalar::is_canonical(bytes))
+    }
+
+    /// True when `bytes` encodes an integer below l, in constant time.
+    pub fn is_canonical(bytes: &[u8; 32]) -> Choice {
+        Scalar::from_bytes_mod_order(bytes).to_bytes().ct_eq(bytes)
+    }
+
+    /// The 32-byte little-endian encoding.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let x = &self.limbs;
+        let words = [x[0] | (x[1] << 52), (x[1] >> 12) | (x[2] << 40), (x[2] >> 24) | (x[3] << 28), (x[3] >> 36) | (x[4] << 16)];
+        let mut result = [0u8; 32];
+        for (i, word) in words.iter().enumerate() {
+            result[8 * i..8 * i + 8].copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+}
+
+impl Scalar {
+    // The width-w non-adjacent form: digits that are zero or odd with |digit| < 2^(w - 1), and
+    // any w consecutive ones holding at most one nonzero digit. For variable-time use only
+    pub(crate) fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
+        debug_assert!((2..=8).contains(&w));
+        let words = self.to_words();
+        let width = 1u64 << w;
+        let window_mask = width - 1;
+        let mut naf = [0i8; 256];
+        let mut position = 0;
+        let mut carry = 0;
+        while position < 256 {
+            let (index, shift) = (position / 64, position % 64);
+            let mut bits = words[index] >> shift;
+            if shift + w > 64 && index < 3 {
+                bits |= words[index + 1] << (64 - shift);
+            }
+            let window = carry + (bits & window_mask);
+            if window & 1 == 0 {
+                position += 1;
+                continue;
+            }
+            if window < width / 2 {
+                carry = 0;
+                naf[position] = window as i8;
+            } else {
+                carry = 1;
+                naf[position] = (window as i8).wrapping_sub(width as i8);
+            }
+            position += w;
+        }
+        naf
+    }
+
+    // Signed radix-2^w digits in [-2^(w - 1), 2^(w - 1)), least significant first. The scalar is
+    // below 2^253, so nothing carries out of the top digit
+    pub(crate) fn as_radix_2w(&self, w: usize) -> Vec<i16> {
+        debug_assert!((4..=8).contains(&w));
+        let words = self.to_words();
+        let radix = 1u64 << w;
+        let count = 256_usize.div_ceil(w);
+        let mut digits = vec![0i16; count];
+        let mut carry = 0;
+        for (i, digit) in digits.iter_mut().enumerate() {
+            let (index, shift) = ((i * w) / 64, (i * w) % 64);
+            let mut bits = words[index] >> shift;
+            if shift + w > 64 && index < 3 {
+                bits |= words[index + 1] << (64 - shift);
+            }
+            let value = carry + (bits & (radix - 1));
+            carry = (value + radix / 2) >> w;
+            *digit = (value as i16) - ((carry << w) as i16);
+        }
+        digits
+    }
+
+    fn to_words(self) -> [u64; 4] {
+        let bytes = self.to_bytes();
+        let mut words = [0u64; 4];
+        for (i, word) in words.iter_mut().enumerate() {
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[8 * i..8 * i + 8]);
+            *word = u64::from_le_bytes(chunk);
+        }
+        words
+    }
+}
+
+// Recodes a little-endian integer below 2^255 (a reduced scalar or a clamped one) into 64
+// digits in [-8, 8), the last in [-8, 8], carrying between neighbours without branching
+pub(crate) fn as_radix_16(bytes: &[u8; 32]) -> [i8; 64] {
+    debug_assert!(bytes[31] <= 127);
+    let mut digits = [0i8; 64];
+    for i in 0..32 {
+        digits[2 * i] = (bytes[i] & 15) as i8;
+        digits[2 * i + 1] = (bytes[i] >> 4) as i8;
+    }
+    for i in 0..63 {
+        let carry = (digits[i] + 8) >> 4;
+        digits[i] -= carry << 4;
+        digits[i + 1] += carry;
+    }
+    digits
+}
+
+// Both inputs below l; the sum is at most 2l - 2 < 2^254, so one conditional subtraction reduces it
+pub(crate) fn sc_add(dest: &mut Scalar, src1: &Scalar, src2: &Scalar) {
+    let mut sum = Scalar::default();
+    let mut carry: u64 = 0;
+    for i in 0..5 {
+        carry = src1.limbs[i] + src2.limbs[i] + (carry >> 52);
+        sum.limbs[i] = carry & MASK52;
+    }
+    sc_sub(dest, &sum, &L);
+}
+
+// src1 - src2, adding l back when the difference is negative; requires -l <= src1 - src2 < l
+pub(crate) fn sc_sub(dest: &mut Scalar, src1: &Scalar, src2: &Scalar) {
+    let mut difference = Scalar::default();
+    let mut borrow: u64 = 0;
+    for i in 0..5 {
+        borrow = src1.limbs[i].wrapping_sub(src2.limbs[i] + (borrow >> 63));
+        difference.limbs[i] = borrow & MASK52;
+    }
+    // All ones when the subtraction wrapped, zero otherwise
+    let underflow_mask = ((borrow >> 63) ^ 1).wrapping_sub(1);
+    let mut carry: u64 = 0;
+    for i in 0..5 {
+        carry = (carry >> 52) + difference.limbs[i] + (L.limbs[i] & underflow_mask);
+        dest.limbs[i] = carry & MASK52;
+    }
+}
+
+pub(crate) fn sc_mul(dest: &mut Scalar, src1: &Scalar, src2: &Scalar) {
+    // (a * b / R) * R^2 / R = a * b
+    let ab = sc_montgomery_mul(src1, src2);
+    *dest = sc_montgomery_mul(&ab, &RR);
+}
+
+// Fermat's little theorem, src^(l - 2), with the square-and-multiply done on Montgomery forms
+// x * R so that each step costs a single reduction. The exponent is public, so the branch on its
+// bits leaks nothing about src; zero maps to zero.
+pub(crate) fn sc_invert(dest: &mut Scalar, src: &Scalar) {
+    let mut exponent = L.to_bytes();
+    exponent[0] -= 2;
+    let mut base = sc_montgomery_mul(src, &RR);
+    let mut result = R;
+    for i in (0..253).rev() {
+        result = sc_montgomery_mul(&result, &result);
+        if (exponent[i / 8] >> (i % 8)) & 1 == 1 {
+            result = sc_montgomery_mul(&result, &base);
+        }
+    }
+    *dest = sc_montgomery_mul(&result, &Scalar::ONE);
+    base.zeroize();
+    result.zeroize();
+}
+
+// a * b / R mod l
+fn sc_montgomery_mul(src1: &Scalar, src2: &Scalar) -> Scalar {
+    sc_montgomery_reduce(&sc_mul_internal(src1, src2))
+}
+
+#[inline(always)]
+fn m(x: u64, y: u64) -> u128 {
+    u128::from(x) * u128::from(y)
+}
+
+// Schoolbook product into nine unreduced 104-bit-ish columns
+fn sc_mul_internal(a: &Scalar, b: &Scalar) -> [u128; 9] {
+    let a = &a.limbs;
+    let b = &b.limbs;
+    [
+        m(a[0], b[0]),
+        m(a[0], b[1]) + m(a[1], b[0]),
+        m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]),
+        m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]),
+        m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]),
+        m(a[1], b[4]) + m(a[2], b[3]) + m(a[3], b[2]) + m(a[4], b[1]),
+        m(a[2], b[4]) + m(a[3], b[3]) + m(a[4], b[2]),
+        m(a[3], b[4]) + m(a[4], b[3]),
+        m(a[4], b[4]),
+    ]
+}
+
+// Computes value / R mod l for value < R * l, one 52-bit limb at a time; l[3] is zero and omitted
+fn sc_montgomery_reduce(limbs: &[u128; 9]) -> Scalar {
+    // Picks the multiple of l that clears the low limb
+    #[inline(always)]
+    fn part1(sum: u128) -> (u128, u64) {
+        let p = (sum as u64).wrapping_mul(LFACTOR) & MASK52;
+        ((sum + m(p, L.limbs[0])) >> 52, p)
+    }
+
+    #[inline(always)]
+    fn part2(sum: u128) -> (u128, u64) {
+        (sum >> 52, (sum as u64) & MASK52)
+    }
+
+    let l = &L.limbs;
+    let (carry, n0) = part1(limbs[0]);
+    let (carry, n1) = part1(carry + limbs[1] + m(n0, l[1]));
+    let (carry, n2) = part1(carry + limbs[2] + m(n0, l[2]) + m(n1, l[1]));
+    let (carry, n3) = part1(carry + limbs[3] + m(n1, l[2]) + m(n2, l[1]));
+    let (carry, n4) = part1(carry + limbs[4] + m(n0, l[4]) + m(n2, l[2]) + m(n3, l[1]));
+    // The low five limbs are now zero, so dividing by R keeps only the upper half
+    let (carry, r0) = part2(carry + limbs[5] + m(n1, l[4]) + m(n3, l[2]) + m(n4, l[1]));
+    let (carry, r1) = part2(carry + limbs[6] + m(n2, l[4]) + m(n4, l[2]));
+    let (carry, r2) = part2(carry + limbs[7] + m(n3, l[4]));
+    let (carry, r3) = part2(carry + limbs[8] + m(n4, l[4]));
+    let r4 = carry as u64;
+    // The quotient is below 2l, so one conditional subtraction finishes
+    let mut result = Scalar::default();
+    sc_sub(&mut result, &Scalar { limbs: [r0, r1, r2, r3, r4] }, &L);
+    result
+}
@@ -0,0 +1,227 @@
+#![deny(clippy::all)]
+
+// Integers mod l = 2^446 - 13818066809895115352007386748515426880336692474882178609894547503885,
+// the prime order of the Ed448 base point (RFC 8032 section 5.2). Seven 64-bit limbs, Montgomery
+// multiplication with R = 2^448; every routine is branch-free and leaves its result fully reduced.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
+
+// l, least significant limb first
+const L: Scalar448 = Scalar448 {
+    limbs: [
+        0x2378_C292_AB58_44F3,
+        0x216C_C272_8DC5_8F55,
+        0xC44E_DB49_AED6_3690,
+        0xFFFF_FFFF_7CCA_23E9,
+        0xFFFF_FFFF_FFFF_FFFF,
+        0xFFFF_FFFF_FFFF_FFFF,
+        0x3FFF_FFFF_FFFF_FFFF,
+    ],
+};
+
+// -1 / l mod 2^64
+const LFACTOR: u64 = 0x03BD_440F_AE91_8BC5;
+
+// R mod l and R^2 mod l
+const R: Scalar448 = Scalar448 { limbs: [0x721C_F5B5_529E_EC34, 0x7A4C_F635_C8E9_C2AB, 0xEEC4_92D9_44A7_25BF, 0x0000_0002_0CD7_7058, 0, 0, 0] };
+const RR: Scalar448 = Scalar448 {
+    limbs: [
+        0xE353_9257_049B_9B60,
+        0x7AF3_2C4B_C1B1_95D9,
+        0x0D66_DE23_88EA_1859,
+        0xAE17_CF72_5EE4_D838,
+        0x1A9C_C14B_A3C4_7C44,
+        0x2052_BCB7_E4D0_70AF,
+        0x3402_A939_F823_B729,
+    ],
+};
+
+/// An integer mod l, the order of the Ed448 base point.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scalar448 {
+    // 7 * 64 = 448; limbs[0] is LSB
+    pub(crate) limbs: [u64; 7],
+}
+
+impl Zeroize for Scalar448 {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl ConstantTimeEq for Scalar448 {
+    fn ct_eq(&self, other: &Scalar448) -> Choice {
+        self.limbs.ct_eq(&other.limbs)
+    }
+}
+
+impl PartialEq for Scalar448 {
+    fn eq(&self, other: &Scalar448) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Scalar448 {}
+
+impl ConditionallySelectable for Scalar448 {
+    fn conditional_select(a: &Scalar448, b: &Scalar448, choice: Choice) -> Scalar448 {
+        let mut result = Scalar448::default();
+        for i in 0..7 {
+            result.limbs[i] = u64::conditional_select(&a.limbs[i], &b.limbs[i], choice);
+        }
+        result
+    }
+}
+
+impl From<u64> for Scalar448 {
+    fn from(value: u64) -> Scalar448 {
+        Scalar448 { limbs: [value, 0, 0, 0, 0, 0, 0] }
+    }
+}
+
+impl Scalar448 {
+    pub const ZERO: Scalar448 = Scalar448 { limbs: [0, 0, 0, 0, 0, 0, 0] };
+    pub const ONE: Scalar448 = Scalar448 { limbs: [1, 0, 0, 0, 0, 0, 0] };
+
+    /// Reduces a 456-bit little-endian integer mod l.
+    pub fn from_bytes_mod_order(bytes: &[u8; 57]) -> Scalar448 {
+        let mut wide = [0u8; 114];
+        wide[..57].copy_from_slice(bytes);
+        let result = Scalar448::from_bytes_mod_order_wide(&wide);
+        wide.zeroize();
+        result
+    }
+
+    /// Reduces a 912-bit little-endian integer mod l, as RFC 8032 does with 114-byte SHAKE256
+    /// outputs.
+    pub fn from_bytes_mod_order_wide(bytes: &[u8; 114]) -> Scalar448 {
+        let mut words = [0u64; 15];
+        for (i, word) in words.iter_mut().enumerate().take(14) {
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[8 * i..8 * i + 8]);
+            *word = u64::from_le_bytes(chunk);
+        }
+        words[14] = u64::from(bytes[112]) | (u64::from(bytes[113]) << 8);
+        // value = lo + mid * R + hi * R^2, with lo and mid 448 bits wide and hi 16
+        let mut lo = Scalar448::default();
+        let mut mid = Scalar448::default();
+        lo.limbs.copy_from_slice(&words[..7]);
+        mid.limbs.copy_from_slice(&words[7..14]);
+        let hi = Scalar448::from(words[14]);
+        // lo * R / R = lo, mid * R^2 / R = mid * R and (hi * R^2 / R) * R^2 / R = hi * R^2
+        let lo_part = sc448_montgomery_mul(&lo, &R);
+        let mid_part = sc448_montgomery_mul(&mid, &RR);
+        let hi_part = sc448_montgomery_mul(&sc448_montgomery_mul(&hi, &RR), &RR);
+        let mut sum = Scalar448::default();
+        let mut result = Scalar448::default();
+        sc448_add(&mut sum, &lo_part, &mid_part);
+        sc448_add(&mut result, &sum, &hi_part);
+        words.zeroize();
+        lo.zeroize();
+        mid.zeroize();
+        result
+    }
+
+    /// Accepts only the encodings of 0..l-1, as RFC 8032 requires of the S half of a signature.
+    pub fn try_from_canonical_bytes(bytes: &[u8; 57]) -> CtOption<Scalar448> {
+        let candidate = Scalar448::from_bytes_mod_order(bytes);
+        CtOption::new(candidate, candidate.to_bytes().ct_eq(bytes))
+    }
+
+    /// The 57-byte little-endian encoding; the last byte is always zero.
+    pub fn to_bytes(&self) -> [u8; 57] {
+        let mut result = [0u8; 57];
+        for (i, limb) in self.limbs.iter().enumerate() {
+            result[8 * i..8 * i + 8].copy_from_slice(&limb.to_le_bytes());
+        }
+        result
+    }
+}
+
+// src1 - src2 over the raw limbs, and the final borrow
+fn sub_limbs(src1: &[u64; 7], src2: &[u64; 7]) -> ([u64; 7], u64) {
+    let mut difference = [0u64; 7];
+    let mut borrow = 0u64;
+    for (limb, (a, b)) in difference.iter_mut().zip(src1.iter().zip(src2.iter())) {
+        let (d1, b1) = a.overflowing_sub(*b);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        *limb = d2;
+        borrow = u64::from(b1 | b2);
+    }
+    (difference, borrow)
+}
+
+// Subtracts l from a value below 2l, keeping the difference only if it did not borrow
+fn subtract_l_if_needed(limbs: &[u64; 7]) -> Scalar448 {
+    let (difference, borrow) = sub_limbs(limbs, &L.limbs);
+    let mut result = Scalar448 { limbs: difference };
+    result.conditional_assign(&Scalar448 { limbs: *limbs }, Choice::from(borrow as u8));
+    result
+}
+
+// Both inputs below l < 2^446, so the sum fits the limbs and one conditional subtraction reduces it
+pub(crate) fn sc448_add(dest: &mut Scalar448, src1: &Scalar448, src2: &Scalar448) {
+    let mut sum = [0u64; 7];
+    let mut carry: u128 = 0;
+    for (limb, (a, b)) in sum.iter_mut().zip(src1.limbs.iter().zip(src2.limbs.iter())) {
+        carry += u128::from(*a) + u128::from(*b);
+        *limb = carry as u64;
+        carry >>= 64;
+    }
+    *dest = subtract_l_if_needed(&sum);
+}
+
+// src1 - src2, adding l back when the difference is negative
+pub(crate) fn sc448_sub(dest: &mut Scalar448, src1: &Scalar448, src2: &Scalar448) {
+    let (difference, borrow) = sub_limbs(&src1.limbs, &src2.limbs);
+    let mask = borrow.wrapping_neg();
+    let mut carry: u128 = 0;
+    for (limb, (d, l)) in dest.limbs.iter_mut().zip(difference.iter().zip(L.limbs.iter())) {
+        carry += u128::from(*d) + u128::from(l & mask);
+        *limb = carry as u64;
+        carry >>= 64;
+    }
+}
+
+pub(crate) fn sc448_mul(dest: &mut Scalar448, src1: &Scalar448, src2: &Scalar448) {
+    // (a * b / R) * R^2 / R = a * b
+    let ab = sc448_montgomery_mul(src1, src2);
+    *dest = sc448_montgomery_mul(&ab, &RR);
+}
+
+// a * b / R mod l, for a * b < R * l: the schoolbook product, then one limb of the quotient
+// at a time chosen to clear the lowest remaining limb
+fn sc448_montgomery_mul(src1: &Scalar448, src2: &Scalar448) -> Scalar448 {
+    let mut wide = [0u64; 14];
+    for i in 0..7 {
+        let mut carry: u128 = 0;
+        for j in 0..7 {
+            carry += u128::from(wide[i + j]) + u128::from(src1.limbs[i]) * u128::from(src2.limbs[j]);
+            wide[i + j] = carry as u64;
+            carry >>= 64;
+        }
+        wide[i + 7] = carry as u64;
+    }
+    let mut top_carry: u128 = 0;
+    for i in 0..7 {
+        let n = wide[i].wrapping_mul(LFACTOR);
+        let mut carry: u128 = 0;
+        for j in 0..7 {
+            carry += u128::from(wide[i + j]) + u128::from(n) * u128::from(L.limbs[j]);
+            wide[i + j] = carry as u64;
+            carry >>= 64;
+        }
+        top_carry += u128::from(wide[i + 7]) + carry;
+        wide[i + 7] = top_carry as u64;
+        top_carry >>= 64;
+    }
+    // The low seven limbs are now zero and the quotient is below 2l < 2^447
+    debug_assert!(top_carry == 0);
+    let mut quotient = [0u64; 7];
+    quotient.copy_from_slice(&wide[7..]);
+    let result = subtract_l_if_needed(&quotient);
+    wide.zeroize();
+    quotient.zeroize();
+    result
+}
@@ -2,11 +2,15 @@
 
 use std::fmt;
 use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
 use regex::Regex;
 
-use crate::arith::Fe25519;
+use crate::arith::{fe_add, fe_invert, fe_mul, fe_square, fe_sub, Fe25519};
+use crate::arith448::{fe448_add, fe448_invert, fe448_mul, fe448_square, fe448_sub, Fe448};
+use crate::scalar::{sc_add, sc_invert, sc_mul, sc_sub, Scalar};
+use crate::scalar448::{sc448_add, sc448_mul, sc448_sub, Scalar448};
 
 impl fmt::Display for Fe25519 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -36,12 +40,29 @@ pub enum ParseError {
     ParseErrorX2(ParseIntError),
     ParseErrorX1(ParseIntError),
     ParseErrorX0(ParseIntError),
+    WrongLength(usize),           // number of hex digits found, 64 expected
+    NonHexCharacter(usize, char), // byte offset into the input string
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::LengthToShort => write!(f, "too few hex digits"),
+            ParseError::LengthToLong => write!(f, "too many hex digits"),
+            ParseError::Missing0x => write!(f, "missing 0x prefix"),
+            ParseError::ParseErrorX3(e) | ParseError::ParseErrorX2(e) | ParseError::ParseErrorX1(e) | ParseError::ParseErrorX0(e) => e.fmt(f),
+            ParseError::WrongLength(digits) => write!(f, "expected 64 hex digits, found {}", digits),
+            ParseError::NonHexCharacter(position, c) => write!(f, "non-hex character {:?} at position {}", c, position),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl FromStr for Fe25519 {
     type Err = self::ParseError;
     fn from_str(hex_str: &str) -> Result<Self, self::ParseError> {
-        if &hex_str[0..2] != "0x" {
+        if !hex_str.starts_with("0x") {
             return Err(self::ParseError::Missing0x);
         };
         let re = Regex::new(r"[^A-Fa-f0-9]").unwrap();
@@ -69,14 +90,301 @@ impl FromStr for Fe25519 {
     }
 }
 
-pub fn check_size(src: &Fe25519) -> bool {
-    if (src.x3 < 0x7FFF_FFFF_FFFF_FFFF)
-        | ((src.x3 == 0x7FFF_FFFF_FFFF_FFFF)
-        & ((src.x2 < 0xFFFF_FFFF_FFFF_FFFF) | (src.x1 < 0xFFFF_FFFF_FFFF_FFFF) | (src.x0 < 0xFFFF_FFFF_FFFF_FFED)))
-    {
-        true
-    } else {
-        println!("Oversize value encountered: {}", src);
-        false
+// Strict parser for "0x" followed by 64 hex digits (the byte string in order), optionally dash-separated
+pub fn hex_to_bytes(hex_str: &str) -> Result<[u8; 32], ParseError> {
+    if !hex_str.starts_with("0x") {
+        return Err(ParseError::Missing0x);
+    }
+    let mut result = [0u8; 32];
+    let mut digits = 0;
+    for (position, c) in hex_str.char_indices().skip(2) {
+        if c == '-' {
+            continue;
+        }
+        let nibble = c.to_digit(16).ok_or(ParseError::NonHexCharacter(position, c))? as u8;
+        if digits < 64 {
+            result[digits / 2] |= nibble << (4 * (1 - digits % 2));
+        }
+        digits += 1;
+    }
+    if digits != 64 {
+        return Err(ParseError::WrongLength(digits));
+    }
+    Ok(result)
+}
+
+// Operators are thin wrappers over the fe_* routines, which write to a separate destination
+impl Add<&Fe25519> for &Fe25519 {
+    type Output = Fe25519;
+    fn add(self, rhs: &Fe25519) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_add(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Sub<&Fe25519> for &Fe25519 {
+    type Output = Fe25519;
+    fn sub(self, rhs: &Fe25519) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_sub(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Mul<&Fe25519> for &Fe25519 {
+    type Output = Fe25519;
+    fn mul(self, rhs: &Fe25519) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_mul(&mut result, self, rhs);
+        result
+    }
+}
+
+// The by-value and mixed forms, plus the assign forms, all defer to the by-reference impls
+macro_rules! forward_binop {
+    ($t:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait<&$t> for $t {
+            type Output = $t;
+            fn $method(self, rhs: &$t) -> $t {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $trait<$t> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: $t) -> $t {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $trait<$t> for $t {
+            type Output = $t;
+            fn $method(self, rhs: $t) -> $t {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $assign_trait<&$t> for $t {
+            fn $assign_method(&mut self, rhs: &$t) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl $assign_trait<$t> for $t {
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+forward_binop!(Fe25519, Add, add, AddAssign, add_assign);
+forward_binop!(Fe25519, Sub, sub, SubAssign, sub_assign);
+forward_binop!(Fe25519, Mul, mul, MulAssign, mul_assign);
+
+impl Neg for &Fe25519 {
+    type Output = Fe25519;
+    fn neg(self) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_sub(&mut result, &Fe25519::default(), self);
+        result
+    }
+}
+
+impl Neg for Fe25519 {
+    type Output = Fe25519;
+    fn neg(self) -> Fe25519 {
+        -&self
+    }
+}
+
+impl Fe25519 {
+    pub fn square(&self) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_square(&mut result, self);
+        result
+    }
+
+    /// The multiplicative inverse mod p; zero maps to zero.
+    pub fn invert(&self) -> Fe25519 {
+        let mut result = Fe25519::default();
+        fe_invert(&mut result, self);
+        result
+    }
+}
+
+// The same operators for scalars mod l, over the sc_* routines
+impl Add<&Scalar> for &Scalar {
+    type Output = Scalar;
+    fn add(self, rhs: &Scalar) -> Scalar {
+        let mut result = Scalar::default();
+        sc_add(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Sub<&Scalar> for &Scalar {
+    type Output = Scalar;
+    fn sub(self, rhs: &Scalar) -> Scalar {
+        let mut result = Scalar::default();
+        sc_sub(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Mul<&Scalar> for &Scalar {
+    type Output = Scalar;
+    fn mul(self, rhs: &Scalar) -> Scalar {
+        let mut result = Scalar::default();
+        sc_mul(&mut result, self, rhs);
+        result
+    }
+}
+
+forward_binop!(Scalar, Add, add, AddAssign, add_assign);
+forward_binop!(Scalar, Sub, sub, SubAssign, sub_assign);
+forward_binop!(Scalar, Mul, mul, MulAssign, mul_assign);
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        let mut result = Scalar::default();
+        sc_sub(&mut result, &Scalar::ZERO, self);
+        result
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        -&self
+    }
+}
+
+impl Scalar {
+    /// The multiplicative inverse mod l; zero maps to zero.
+    pub fn invert(&self) -> Scalar {
+        let mut result = Scalar::default();
+        sc_invert(&mut result, self);
+        result
+    }
+}
+
+// And for the X448 field, over the fe448_* routines
+impl fmt::Debug for Fe448 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limbs: Vec<String> = self.limbs.iter().rev().map(|limb| format!("{:016x}", limb)).collect();
+        write!(f, "0x{}", limbs.join("-"))
+    }
+}
+
+impl Add<&Fe448> for &Fe448 {
+    type Output = Fe448;
+    fn add(self, rhs: &Fe448) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_add(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Sub<&Fe448> for &Fe448 {
+    type Output = Fe448;
+    fn sub(self, rhs: &Fe448) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_sub(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Mul<&Fe448> for &Fe448 {
+    type Output = Fe448;
+    fn mul(self, rhs: &Fe448) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_mul(&mut result, self, rhs);
+        result
+    }
+}
+
+forward_binop!(Fe448, Add, add, AddAssign, add_assign);
+forward_binop!(Fe448, Sub, sub, SubAssign, sub_assign);
+forward_binop!(Fe448, Mul, mul, MulAssign, mul_assign);
+
+impl Neg for &Fe448 {
+    type Output = Fe448;
+    fn neg(self) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_sub(&mut result, &Fe448::ZERO, self);
+        result
+    }
+}
+
+impl Neg for Fe448 {
+    type Output = Fe448;
+    fn neg(self) -> Fe448 {
+        -&self
+    }
+}
+
+impl Fe448 {
+    pub fn square(&self) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_square(&mut result, self);
+        result
+    }
+
+    /// The multiplicative inverse mod p; zero maps to zero.
+    pub fn invert(&self) -> Fe448 {
+        let mut result = Fe448::default();
+        fe448_invert(&mut result, self);
+        result
+    }
+}
+
+// And for scalars mod the Ed448 group order, over the sc448_* routines
+impl Add<&Scalar448> for &Scalar448 {
+    type Output = Scalar448;
+    fn add(self, rhs: &Scalar448) -> Scalar448 {
+        let mut result = Scalar448::default();
+        sc448_add(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Sub<&Scalar448> for &Scalar448 {
+    type Output = Scalar448;
+    fn sub(self, rhs: &Scalar448) -> Scalar448 {
+        let mut result = Scalar448::default();
+        sc448_sub(&mut result, self, rhs);
+        result
+    }
+}
+
+impl Mul<&Scalar448> for &Scalar448 {
+    type Output = Scalar448;
+    fn mul(self, rhs: &Scalar448) -> Scalar448 {
+        let mut result = Scalar448::default();
+        sc448_mul(&mut result, self, rhs);
+        result
+    }
+}
+
+forward_binop!(Scalar448, Add, add, AddAssign, add_assign);
+forward_binop!(Scalar448, Sub, sub, SubAssign, sub_assign);
+forward_binop!(Scalar448, Mul, mul, MulAssign, mul_assign);
+
+impl Neg for &Scalar448 {
+    type Output = Scalar448;
+    fn neg(self) -> Scalar448 {
+        let mut result = Scalar448::default();
+        sc448_sub(&mut result, &Scalar448::ZERO, self);
+        result
+    }
+}
+
+impl Neg for Scalar448 {
+    type Output = Scalar448;
+    fn neg(self) -> Scalar448 {
+        -&self
     }
 }
@@ -7,17 +7,51 @@ use std::time::Instant;
 use num_bigint::{BigUint, RandomBits};
 use num_traits::One;
 use rand::Rng;
+use sha2::{Digest, Sha512};
 
-use crate::arith::{Fe25519, fe_add, fe_invert, fe_mul, fe_mul_121665, fe_square, fe_sub, get_k, get_u, mul};
+use crate::arith::{Fe25519, fe_add, fe_invert, fe_mul, fe_mul_121665, fe_square, fe_sub, get_k, get_u, mul, try_get_k, try_get_u};
+use crate::arith448::fe448_mul_39081;
+use crate::crypto_box::crypto_box_seal_with;
+use crate::hpke::{setup_sender, Mode};
+use crate::support::ParseError;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+use crate::{
+    crypto_box_beforenm, crypto_box_easy, crypto_box_easy_afternm, crypto_box_keypair, crypto_box_open_easy, crypto_box_open_easy_afternm,
+    crypto_box_seal, crypto_box_seal_open, derive_key_pair, ed25519ph_prehash, ed448ph_prehash, elligator2_inverse, elligator2_keypair,
+    elligator2_map, elligator2_public_key, elligator2_representative, encode_to_curve25519, encode_to_edwards25519, expand_message_xmd,
+    hash_to_curve25519, hash_to_edwards25519, hash_to_field, setup_auth_psk_r, setup_auth_psk_s, setup_auth_r, setup_auth_s, setup_base_r,
+    setup_base_s, setup_psk_r, setup_psk_s, verify_batch, x25519, x25519_base, x25519_batch, x448, CryptoBoxError, Ed448Signature, Ed448SigningKey,
+    Ed448VerifyingKey, Edwards448Point, EdwardsPoint, EphemeralSecret, Fe448, HashToCurveError, HpkeAead, HpkeError, HpkeKdf, HpkeReceiverContext,
+    HpkeSuite, KeyAgreementError, PublicKey, RistrettoPoint, Scalar, Scalar448, Signature, SignatureError, SigningKey, StaticSecret, VerifyingKey,
+    CRYPTO_BOX_MACBYTES, CRYPTO_BOX_NONCEBYTES, CRYPTO_BOX_SEALBYTES, ED25519_BASEPOINT, ED448_BASEPOINT, RISTRETTO_BASEPOINT, X25519_BASEPOINT,
+    X448_BASEPOINT,
+};
 
 lazy_static! {
     static ref TWO255M19: BigUint = {
         let one: BigUint = One::one();
-        let two255m19 = (one << 255).sub(19 as u32);
-        two255m19
+        (one << 255).sub(19_u32)
     };
 }
 
+pub fn hex_bytes(hex_str: &str) -> Vec<u8> {
+    (0..hex_str.len()).step_by(2).map(|i| u8::from_str_radix(&hex_str[i..i + 2], 16).unwrap()).collect()
+}
+
+pub fn hex_32(hex_str: &str) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&hex_bytes(hex_str));
+    result
+}
+
+pub fn fe_from_big(src: &BigUint) -> Fe25519 {
+    let mut bytes = [0u8; 32];
+    let le_bytes = src.to_bytes_le();
+    bytes[..le_bytes.len()].copy_from_slice(&le_bytes);
+    Fe25519::from_bytes(&bytes)
+}
+
 pub fn generate_operand(bits: usize) -> BigUint {
     let mut rng = rand::thread_rng();
     let mut result: BigUint;
@@ -27,10 +61,10 @@ pub fn generate_operand(bits: usize) -> BigUint {
             1 => BigUint::from_str("1").unwrap(),
             2 => BigUint::from_str("2").unwrap(),
             3 => BigUint::from_str("3").unwrap(),
-            4 => (&*TWO255M19).clone().sub(4 as u32),
-            5 => (&*TWO255M19).clone().sub(3 as u32),
-            6 => (&*TWO255M19).clone().sub(2 as u32),
-            7 => (&*TWO255M19).clone().sub(1 as u32),
+            4 => (*TWO255M19).clone().sub(4_u32),
+            5 => (*TWO255M19).clone().sub(3_u32),
+            6 => (*TWO255M19).clone().sub(2_u32),
+            7 => (*TWO255M19).clone().sub(1_u32),
             _ => rng.sample(RandomBits::new(bits)),
         };
         if result < *TWO255M19 {
@@ -109,6 +143,32 @@ fn fuzz_mul_121665() {
     }
 }
 
+#[test]
+fn edge_case_arith() {
+    // Operands near p and 2**255 that push the reductions into their carry and rollover paths
+    let mut operands: Vec<BigUint> = (0_u32..40).map(BigUint::from).collect();
+    operands.extend((1_u32..40).map(|i| (*TWO255M19).clone().sub(i)));
+    operands.extend((1_u32..20).map(|i| (BigUint::one() << 254).sub(i)));
+    operands.extend((0_u32..20).map(|i| (BigUint::one() << 128) + BigUint::from(i)));
+    let mut act = Fe25519::default();
+    for a_exp in operands.iter() {
+        let a = fe_from_big(a_exp);
+        fe_square(&mut act, &a);
+        assert_eq!(act, fe_from_big(&((a_exp * a_exp) % &*TWO255M19)));
+        fe_mul_121665(&mut act, &a);
+        assert_eq!(act, fe_from_big(&((a_exp * 121_665_u32) % &*TWO255M19)));
+        for b_exp in operands.iter() {
+            let b = fe_from_big(b_exp);
+            fe_mul(&mut act, &a, &b);
+            assert_eq!(act, fe_from_big(&((a_exp * b_exp) % &*TWO255M19)));
+            fe_add(&mut act, &a, &b);
+            assert_eq!(act, fe_from_big(&((a_exp + b_exp) % &*TWO255M19)));
+            fe_sub(&mut act, &a, &b);
+            assert_eq!(act, fe_from_big(&((a_exp + &*TWO255M19 - b_exp) % &*TWO255M19)));
+        }
+    }
+}
+
 #[test]
 fn fuzz_inverse() {
     let one = Fe25519::from_str("0x0000000000000000-0000000000000000-0000000000000000-0000000000000001").unwrap();
@@ -126,6 +186,189 @@ fn fuzz_inverse() {
     }
 }
 
+#[test]
+fn fuzz_bytes() {
+    for _index in 1..1_000 {
+        let a_exp = generate_operand(256);
+        let a_act = Fe25519::from_str(&format!("0x{:064x}", a_exp)).unwrap();
+        let mut bytes_exp = a_exp.to_bytes_le();
+        bytes_exp.resize(32, 0);
+        assert_eq!(a_act.to_bytes().to_vec(), bytes_exp);
+        assert_eq!(Fe25519::from_bytes(&a_act.to_bytes()), a_act);
+    }
+}
+
+#[test]
+fn non_canonical_bytes() {
+    // p, p + 1 and 2**255 - 1 reduce; bit 255 is ignored
+    let p = hex_32("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let p_plus_1 = hex_32("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let all_ones = [0xffu8; 32];
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    let mut one_high = one;
+    one_high[31] = 0x80;
+    let mut eighteen = [0u8; 32];
+    eighteen[0] = 18;
+    assert_eq!(Fe25519::from_bytes(&p).to_bytes(), [0u8; 32]);
+    assert_eq!(Fe25519::from_bytes(&p_plus_1).to_bytes(), one);
+    assert_eq!(Fe25519::from_bytes(&all_ones).to_bytes(), eighteen);
+    assert_eq!(Fe25519::from_bytes(&one_high).to_bytes(), one);
+
+    // Values held above p are still encoded canonically
+    let over = Fe25519 { x3: 0xFFFF_FFFF_FFFF_FFFF, x2: 0xFFFF_FFFF_FFFF_FFFF, x1: 0xFFFF_FFFF_FFFF_FFFF, x0: 0xFFFF_FFFF_FFFF_FFFF };
+    let mut thirty_seven = [0u8; 32];
+    thirty_seven[0] = 37;
+    assert_eq!(over.to_bytes(), thirty_seven);
+}
+
+#[test]
+fn canonical_checks() {
+    let p_minus_1 = hex_32("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let p = hex_32("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let mut nine_high = X25519_BASEPOINT;
+    nine_high[31] = 0x80;
+    assert!(bool::from(Fe25519::try_from_canonical_bytes(&p_minus_1).is_some()));
+    assert_eq!(Fe25519::try_from_canonical_bytes(&X25519_BASEPOINT).unwrap().to_bytes(), X25519_BASEPOINT);
+    assert!(bool::from(Fe25519::try_from_canonical_bytes(&p).is_none()));
+    assert!(bool::from(Fe25519::try_from_canonical_bytes(&[0xffu8; 32]).is_none()));
+    assert!(bool::from(Fe25519::try_from_canonical_bytes(&nine_high).is_none()));
+
+    assert!(bool::from(Fe25519::from_bytes(&p_minus_1).is_canonical()));
+    let over = Fe25519 { x3: 0x7FFF_FFFF_FFFF_FFFF, x2: 0xFFFF_FFFF_FFFF_FFFF, x1: 0xFFFF_FFFF_FFFF_FFFF, x0: 0xFFFF_FFFF_FFFF_FFED };
+    assert!(!bool::from(over.is_canonical()));
+    let top = Fe25519 { x3: 0x8000_0000_0000_0000, x2: 0, x1: 0, x0: 0 };
+    assert!(!bool::from(top.is_canonical()));
+}
+
+#[test]
+fn constant_time_ops() {
+    let a = Fe25519::from_bytes(&hex_32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"));
+    let b = Fe25519::from_bytes(&X25519_BASEPOINT);
+    assert!(bool::from(a.ct_eq(&a)));
+    assert!(!bool::from(a.ct_eq(&b)));
+    let p_plus_9 = Fe25519 { x3: 0x7FFF_FFFF_FFFF_FFFF, x2: 0xFFFF_FFFF_FFFF_FFFF, x1: 0xFFFF_FFFF_FFFF_FFFF, x0: 0xFFFF_FFFF_FFFF_FFF6 };
+    assert!(bool::from(p_plus_9.ct_eq(&b)));
+
+    assert_eq!(Fe25519::conditional_select(&a, &b, Choice::from(0)), a);
+    assert_eq!(Fe25519::conditional_select(&a, &b, Choice::from(1)), b);
+    let (mut c, mut d) = (a, b);
+    Fe25519::conditional_swap(&mut c, &mut d, Choice::from(0));
+    assert_eq!((c, d), (a, b));
+    Fe25519::conditional_swap(&mut c, &mut d, Choice::from(1));
+    assert_eq!((c, d), (b, a));
+
+    let mut negated = a;
+    negated.conditional_negate(Choice::from(0));
+    assert_eq!(negated, a);
+    negated.conditional_negate(Choice::from(1));
+    let mut sum = Fe25519::default();
+    fe_add(&mut sum, &a, &negated);
+    assert_eq!(sum, Fe25519::default());
+    let mut zero = Fe25519::default();
+    zero.conditional_negate(Choice::from(1));
+    assert_eq!(zero, Fe25519::default());
+}
+
+#[test]
+#[allow(clippy::op_ref)] // exercise the by-reference impls too
+fn fuzz_operators() {
+    let mut expected = Fe25519::default();
+    for _index in 1..1_000 {
+        let a = Fe25519::from_str(&format!("0x{:064x}", generate_operand(256))).unwrap();
+        let b = Fe25519::from_str(&format!("0x{:064x}", generate_operand(256))).unwrap();
+        fe_add(&mut expected, &a, &b);
+        assert_eq!(a + b, expected);
+        assert_eq!(&a + &b, expected);
+        let mut c = a;
+        c += &b;
+        assert_eq!(c, expected);
+        fe_sub(&mut expected, &a, &b);
+        assert_eq!(a - b, expected);
+        assert_eq!(&a - b, expected);
+        let mut c = a;
+        c -= b;
+        assert_eq!(c, expected);
+        fe_mul(&mut expected, &a, &b);
+        assert_eq!(a * b, expected);
+        assert_eq!(a * &b, expected);
+        let mut c = a;
+        c *= &b;
+        assert_eq!(c, expected);
+        assert_eq!(a.square(), a * a);
+        assert_eq!(-a + a, Fe25519::default());
+        assert_eq!(-(-a), a);
+        if a != Fe25519::default() {
+            assert_eq!(a * a.invert(), Fe25519::from_bytes(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        }
+    }
+}
+
+#[test]
+fn fuzz_sqrt_ratio() {
+    let one: BigUint = One::one();
+    let legendre_exp = (&*TWO255M19 - &one) >> 1;
+    let pow22523_exp = (one.clone() << 252).sub(3_u32);
+    let sqrt_m1 = Fe25519::from_str("0x2b8324804fc1df0b-2b4d00993dfbd7a7-2f431806ad2fe478-c4ee1b274a0ea0b0").unwrap();
+    for _index in 1..200 {
+        let u_exp = generate_operand(256);
+        let v_exp = generate_operand(256);
+        let u = Fe25519::from_str(&format!("0x{:064x}", u_exp)).unwrap();
+        let v = Fe25519::from_str(&format!("0x{:064x}", v_exp)).unwrap();
+        let pow_exp = Fe25519::from_str(&format!("0x{:064x}", u_exp.modpow(&pow22523_exp, &TWO255M19))).unwrap();
+        assert_eq!(u.pow22523(), pow_exp);
+        let is_square = u_exp == BigUint::from(0_u32) || u_exp.modpow(&legendre_exp, &TWO255M19) == one;
+        assert_eq!(bool::from(u.is_square()), is_square);
+
+        // Squares come back with a non-negative root
+        let (was_square, root) = Fe25519::sqrt_ratio(&(u.square() * v), &v);
+        assert!(bool::from(was_square) || v == Fe25519::ZERO);
+        if v != Fe25519::ZERO {
+            assert!(root == u || root == -u);
+            assert!(!bool::from(root.is_negative()));
+        }
+
+        let (was_square, root) = Fe25519::sqrt_ratio(&u, &v);
+        if v == Fe25519::ZERO {
+            assert_eq!(root, Fe25519::ZERO);
+            assert_eq!(bool::from(was_square), u == Fe25519::ZERO);
+        } else if bool::from(was_square) {
+            assert_eq!(root.square() * v, u);
+        } else {
+            assert_eq!(root.square() * v, sqrt_m1 * u);
+        }
+    }
+    assert_eq!(sqrt_m1.square(), -Fe25519::ONE);
+}
+
+#[test]
+fn batch_invert() {
+    let mut inputs: Vec<Fe25519> = (0..20).map(|_| fe_from_big(&generate_operand(256))).collect();
+    inputs[3] = Fe25519::ZERO;
+    inputs[19] = Fe25519::ZERO;
+    let expected: Vec<Fe25519> = inputs.iter().map(Fe25519::invert).collect();
+    Fe25519::batch_invert(&mut inputs);
+    assert_eq!(inputs, expected);
+    Fe25519::batch_invert(&mut []);
+}
+
+#[test]
+fn x25519_batched() {
+    let mut rng = rand::thread_rng();
+    let mut scalars: Vec<[u8; 32]> = (0..8).map(|_| rng.gen()).collect();
+    let mut points: Vec<[u8; 32]> = (0..8).map(|_| rng.gen()).collect();
+    scalars.push(hex_32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"));
+    points.push(hex_32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"));
+    scalars.push(hex_32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"));
+    points.push([0u8; 32]); // ladder ends with z = 0
+    let results = x25519_batch(&scalars, &points);
+    for i in 0..scalars.len() {
+        assert_eq!(results[i], x25519(scalars[i], points[i]));
+    }
+    assert_eq!(results[8], hex_32("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"));
+    assert_eq!(results[9], [0u8; 32]);
+}
+
 #[test]
 fn iterative_mul() {
     let k = get_k("0x0900000000000000-0000000000000000-0000000000000000-0000000000000000");
@@ -179,3 +422,1567 @@ fn iterative_mul() {
     let duration = start_time.elapsed();
     println!("Mul rate is {:3.3}k per second.", 5_000.0 / duration.as_millis() as f64);
 }
+
+#[test]
+fn fallible_parsing() {
+    let good = "0xa546e36bf0527c9d-3b16154b82465edd-62144c0ac1fc5a18-506a2244ba449ac4";
+    assert_eq!(try_get_k(good).unwrap(), get_k(good));
+    assert_eq!(try_get_u("0xe6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c").unwrap(),
+               get_u("0xe6db6867583030db-3594c1a424b15f7c-726624ec26b3353b-10a903a6d0ab1c4c"));
+    match try_get_u("") {
+        Err(ParseError::Missing0x) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match try_get_u("0x0900000000000000-0000000000000000-0000000000000000") {
+        Err(ParseError::WrongLength(48)) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match try_get_k("0x0900000000000000-0000000000000000-0000000000000000-00000000000000000") {
+        Err(ParseError::WrongLength(65)) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match try_get_u("0x0900000000000000-00000000000g0000-0000000000000000-0000000000000000") {
+        Err(ParseError::NonHexCharacter(30, 'g')) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn x25519_rfc7748() {
+    // Section 5.2 test vectors
+    let k = hex_32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+    let u = hex_32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+    assert_eq!(x25519(k, u), hex_32("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"));
+    let k = hex_32("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d");
+    let u = hex_32("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493");
+    assert_eq!(x25519(k, u), hex_32("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957"));
+
+    // Section 6.1 Diffie-Hellman
+    let alice_private = hex_32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob_private = hex_32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    let alice_public = x25519(alice_private, X25519_BASEPOINT);
+    let bob_public = x25519(bob_private, X25519_BASEPOINT);
+    assert_eq!(alice_public, hex_32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(bob_public, hex_32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+    let shared = hex_32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(x25519(alice_private, bob_public), shared);
+    assert_eq!(x25519(bob_private, alice_public), shared);
+}
+
+#[test]
+fn x25519_fixed_base() {
+    // RFC 7748 section 6.1 public keys
+    let alice_private = hex_32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob_private = hex_32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    assert_eq!(x25519_base(alice_private), hex_32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(x25519_base(bob_private), hex_32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+
+    // Scalars whose digits all sit at the ends of the signed radix-16 range
+    let mut rng = rand::thread_rng();
+    let mut scalars: Vec<[u8; 32]> = vec![[0u8; 32], [0xFF; 32], [0x88; 32], [0x77; 32], [0x08; 32]];
+    scalars.extend((0..32).map(|_| rng.gen::<[u8; 32]>()));
+    for scalar in scalars.iter() {
+        assert_eq!(x25519_base(*scalar), x25519(*scalar, X25519_BASEPOINT));
+        assert_eq!(EdwardsPoint::mul_base_clamped(*scalar), ED25519_BASEPOINT.mul_clamped(*scalar));
+        let s = Scalar::from_bytes_mod_order(scalar);
+        assert_eq!(EdwardsPoint::mul_base(&s), ED25519_BASEPOINT * &s);
+    }
+    assert!(EdwardsPoint::mul_base(&Scalar::ZERO).is_identity());
+    assert_eq!(EdwardsPoint::mul_base(&-Scalar::ONE), -ED25519_BASEPOINT);
+
+    // Again a relative metric only
+    let start_time = Instant::now();
+    for scalar in scalars.iter() {
+        x25519(*scalar, X25519_BASEPOINT);
+    }
+    let ladder = start_time.elapsed();
+    let start_time = Instant::now();
+    for scalar in scalars.iter() {
+        x25519_base(*scalar);
+    }
+    let fixed_base = start_time.elapsed();
+    println!("Fixed-base speedup is {:2.1}x.", ladder.as_nanos() as f64 / fixed_base.as_nanos() as f64);
+}
+
+fn fe448_from_big(src: &BigUint) -> Fe448 {
+    let mut bytes = [0u8; 56];
+    let le_bytes = src.to_bytes_le();
+    bytes[..le_bytes.len()].copy_from_slice(&le_bytes);
+    Fe448::from_bytes(&bytes)
+}
+
+fn hex_56(hex_str: &str) -> [u8; 56] {
+    let mut result = [0u8; 56];
+    result.copy_from_slice(&hex_bytes(hex_str));
+    result
+}
+
+#[test]
+fn fuzz_fe448() {
+    let one: BigUint = One::one();
+    let p448 = (one.clone() << 448) - (one.clone() << 224) - one;
+    let mut rng = rand::thread_rng();
+    let mut operands: Vec<BigUint> = vec![BigUint::from(0_u32), BigUint::from(1_u32), p448.clone() - 1_u32, p448.clone() - 2_u32];
+    operands.push(BigUint::from_str("26959946667150639794667015087019630673637144422540572481103610249215").unwrap()); // 2**224 - 1
+    for _index in 0..40 {
+        let operand: BigUint = rng.sample(RandomBits::new(448));
+        operands.push(operand % &p448);
+    }
+    for a in operands.iter() {
+        let fe_a = fe448_from_big(a);
+        assert_eq!(BigUint::from_bytes_le(&fe_a.to_bytes()), *a);
+        for b in operands.iter().take(12) {
+            let fe_b = fe448_from_big(b);
+            assert_eq!(fe_a + fe_b, fe448_from_big(&((a + b) % &p448)));
+            assert_eq!(fe_a - fe_b, fe448_from_big(&((a + &p448 - b) % &p448)));
+            assert_eq!(fe_a * fe_b, fe448_from_big(&((a * b) % &p448)));
+        }
+        assert_eq!(fe_a.square(), fe448_from_big(&((a * a) % &p448)));
+        let mut scaled = Fe448::default();
+        fe448_mul_39081(&mut scaled, &fe_a);
+        assert_eq!(scaled, fe448_from_big(&((a * 39_081_u32) % &p448)));
+        if *a != BigUint::from(0_u32) {
+            assert_eq!(fe_a * fe_a.invert(), Fe448::ONE);
+        }
+    }
+    assert_eq!(Fe448::ZERO.invert(), Fe448::ZERO);
+
+    // Encodings of p and above reduce, or are rejected by the strict decoder
+    let mut p_bytes = [0u8; 56];
+    p_bytes.copy_from_slice(&p448.to_bytes_le());
+    assert_eq!(Fe448::from_bytes(&p_bytes), Fe448::ZERO);
+    assert!(bool::from(Fe448::try_from_canonical_bytes(&p_bytes).is_none()));
+    assert_eq!(Fe448::from_bytes(&[0xFF; 56]), fe448_from_big(&(BigUint::from(1_u32) << 224))); // 2**448 - 1 - p
+    p_bytes[0] -= 1;
+    assert_eq!(Fe448::try_from_canonical_bytes(&p_bytes).unwrap(), fe448_from_big(&(p448 - 1_u32)));
+}
+
+#[test]
+fn x448_rfc7748() {
+    // Section 5.2 test vectors
+    let k = hex_56("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3");
+    let u = hex_56("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086");
+    let expected = hex_56("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f");
+    assert_eq!(x448(k, u)[..], expected[..]);
+    let k = hex_56("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f");
+    let u = hex_56("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db");
+    let expected = hex_56("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d");
+    assert_eq!(x448(k, u)[..], expected[..]);
+
+    // Iterated: k = u = 5, then u = previous k and k = previous result
+    let mut k = X448_BASEPOINT;
+    let mut u = X448_BASEPOINT;
+    for index in 0..1_000 {  // Set to 1M for full test
+        let result = x448(k, u);
+        u = k;
+        k = result;
+        match index {
+            0 => assert_eq!(k[..], hex_bytes("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113")[..]),
+            999 => assert_eq!(k[..], hex_bytes("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38")[..]),
+            999_999 => assert_eq!(k[..], hex_bytes("077f453681caca3693198420bbe515cae0002472519b3e67661a7e89cab94695c8f4bcd66e61b9b9c946da8d524de3d69bd9d9d66b997e37")[..]),
+            _ => {}
+        }
+    }
+
+    // Section 6.2 Diffie-Hellman
+    let alice_private = hex_56("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b");
+    let bob_private = hex_56("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d");
+    let alice_public = x448(alice_private, X448_BASEPOINT);
+    let bob_public = x448(bob_private, X448_BASEPOINT);
+    assert_eq!(alice_public[..], hex_bytes("9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0")[..]);
+    assert_eq!(bob_public[..], hex_bytes("3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609")[..]);
+    let shared = hex_bytes("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d");
+    assert_eq!(x448(alice_private, bob_public)[..], shared[..]);
+    assert_eq!(x448(bob_private, alice_public)[..], shared[..]);
+}
+
+#[test]
+fn key_agreement() {
+    let alice = StaticSecret::from(hex_32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"));
+    let bob = StaticSecret::from(hex_32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb"));
+    let alice_public = PublicKey::from(&alice);
+    let bob_public = PublicKey::from(&bob);
+    assert_eq!(alice_public.to_bytes(), hex_32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    let shared = hex_32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice.diffie_hellman(&bob_public).to_bytes(), shared);
+    assert_eq!(bob.diffie_hellman(&alice_public).as_bytes(), &shared);
+    assert_eq!(alice.to_bytes()[0] & 7, 0);
+
+    let mut rng = rand::thread_rng();
+    let ephemeral = EphemeralSecret::random_from_rng(&mut rng);
+    let ephemeral_public = PublicKey::from(&ephemeral);
+    let static_secret = StaticSecret::random_from_rng(&mut rng);
+    let expected = static_secret.diffie_hellman(&ephemeral_public);
+    assert_eq!(ephemeral.diffie_hellman(&PublicKey::from(&static_secret)).to_bytes(), expected.to_bytes());
+}
+
+#[test]
+fn low_order_rejection() {
+    // libsodium's blocklist: 0, 1, the two order-8 points, p - 1, p and p + 1
+    let mut low_order: Vec<[u8; 32]> = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800",
+        "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157",
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ]
+    .iter()
+    .map(|u| hex_32(u))
+    .collect();
+    // The same encodings with bit 255 set, which X25519 ignores
+    let with_high_bit: Vec<[u8; 32]> = low_order
+        .iter()
+        .map(|u| {
+            let mut u = *u;
+            u[31] |= 0x80;
+            u
+        })
+        .collect();
+    low_order.extend(with_high_bit);
+
+    let mut rng = rand::thread_rng();
+    let secret = StaticSecret::random_from_rng(&mut rng);
+    for u in low_order.iter() {
+        let public = PublicKey::from(*u);
+        assert!(bool::from(public.is_low_order()));
+        assert_eq!(secret.diffie_hellman(&public).to_bytes(), [0u8; 32]);
+        assert_eq!(secret.diffie_hellman_checked(&public).err(), Some(KeyAgreementError::NonContributory));
+        let ephemeral = EphemeralSecret::random_from_rng(&mut rng);
+        assert!(ephemeral.diffie_hellman_checked(&public).is_err());
+    }
+
+    let peer = StaticSecret::random_from_rng(&mut rng);
+    let peer_public = PublicKey::from(&peer);
+    assert!(!bool::from(peer_public.is_low_order()));
+    let shared = secret.diffie_hellman_checked(&peer_public).unwrap();
+    assert_eq!(shared.to_bytes(), peer.diffie_hellman(&PublicKey::from(&secret)).to_bytes());
+}
+
+#[test]
+fn zeroize_secrets() {
+    let mut k = get_k("0xa546e36bf0527c9d-3b16154b82465edd-62144c0ac1fc5a18-506a2244ba449ac4");
+    k.zeroize();
+    assert_eq!(k, Fe25519::default());
+    let mut secret = StaticSecret::from(hex_32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"));
+    secret.zeroize();
+    assert_eq!(secret.to_bytes(), [0u8; 32]);
+}
+
+
+#[test]
+fn edwards_group_law() {
+    let b = ED25519_BASEPOINT;
+    let identity = EdwardsPoint::identity();
+    assert_eq!(b.compress(), hex_32("5866666666666666666666666666666666666666666666666666666666666666"));
+    assert_eq!(identity.compress(), hex_32("0100000000000000000000000000000000000000000000000000000000000000"));
+    assert_eq!(b + identity, b);
+    assert_eq!(b + b, b.double());
+    assert_eq!(b.double() + b, b + b.double());
+    assert_eq!(b - b, identity);
+    assert!((b + (-b)).is_identity());
+    assert_eq!(-(-b), b);
+    assert!(!b.is_small_order());
+    assert!(identity.is_small_order());
+
+    let mut p = identity;
+    for _i in 0..16 {
+        assert_eq!(EdwardsPoint::decompress(&p.compress()), Some(p));
+        p += &b;
+    }
+    assert_eq!(p, b.mul_by_pow_2(4));
+}
+
+#[test]
+fn edwards_decompress_rejects() {
+    // y = p is non-canonical, y = 2 gives a non-square x^2, and x = 0 cannot be negative
+    assert_eq!(EdwardsPoint::decompress(&hex_32("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")), None);
+    assert_eq!(EdwardsPoint::decompress(&hex_32("0200000000000000000000000000000000000000000000000000000000000000")), None);
+    assert_eq!(EdwardsPoint::decompress(&hex_32("0100000000000000000000000000000000000000000000000000000000000080")), None);
+}
+
+#[test]
+fn edwards_montgomery_maps() {
+    let nine = Fe25519::from_bytes(&X25519_BASEPOINT);
+    assert_eq!(ED25519_BASEPOINT.to_montgomery(), nine);
+    assert_eq!(EdwardsPoint::from_montgomery(&nine, Choice::from(0)), Some(ED25519_BASEPOINT));
+    assert_eq!(EdwardsPoint::from_montgomery(&nine, Choice::from(1)), Some(-ED25519_BASEPOINT));
+    assert_eq!(EdwardsPoint::from_montgomery(&-Fe25519::ONE, Choice::from(0)), None);
+
+    let mut rng = rand::thread_rng();
+    for _index in 0..4 {
+        let k: [u8; 32] = rng.gen();
+        let p = ED25519_BASEPOINT.mul_clamped(k);
+        assert_eq!(p.to_montgomery().to_bytes(), x25519(k, X25519_BASEPOINT));
+        let q = EdwardsPoint::from_montgomery(&p.to_montgomery(), Choice::from(p.compress()[31] >> 7)).unwrap();
+        assert_eq!(q, p);
+    }
+}
+
+// l = 2^252 + 27742317777372353535851937790883648493, little-endian
+const ED25519_ORDER: &str = "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+
+#[test]
+fn fuzz_scalar() {
+    let order = BigUint::from_bytes_le(&hex_bytes(ED25519_ORDER));
+    let mut rng = rand::thread_rng();
+    for _index in 0..10_000 {
+        let mut wide1 = [0u8; 64];
+        let mut wide2 = [0u8; 64];
+        rng.fill(&mut wide1[..]);
+        rng.fill(&mut wide2[..]);
+        let a = Scalar::from_bytes_mod_order_wide(&wide1);
+        let b = Scalar::from_bytes_mod_order_wide(&wide2);
+        let big_a = BigUint::from_bytes_le(&wide1) % &order;
+        let big_b = BigUint::from_bytes_le(&wide2) % &order;
+        let big = |scalar: Scalar| BigUint::from_bytes_le(&scalar.to_bytes());
+        assert_eq!(big(a), big_a);
+        assert_eq!(big(a + b), (&big_a + &big_b) % &order);
+        assert_eq!(big(a - b), (&big_a + &order - &big_b) % &order);
+        assert_eq!(big(a * b), (&big_a * &big_b) % &order);
+        assert_eq!(big(-a), (&order - &big_a) % &order);
+        let mut narrow = [0u8; 32];
+        narrow.copy_from_slice(&wide1[..32]);
+        assert_eq!(big(Scalar::from_bytes_mod_order(&narrow)), BigUint::from_bytes_le(&narrow) % &order);
+        assert_eq!(Scalar::try_from_canonical_bytes(&a.to_bytes()).unwrap(), a);
+    }
+    for _index in 0..100 {
+        let a = Scalar::from_bytes_mod_order(&rng.gen());
+        if a != Scalar::ZERO {
+            assert_eq!(a * a.invert(), Scalar::ONE);
+        }
+    }
+    assert_eq!(Scalar::ZERO.invert(), Scalar::ZERO);
+    assert_eq!(Scalar::from(2) * Scalar::from(3), Scalar::from(6));
+    assert_eq!(-Scalar::ONE + Scalar::ONE, Scalar::ZERO);
+
+    let mut l_minus_1 = hex_32(ED25519_ORDER);
+    l_minus_1[0] -= 1;
+    assert!(bool::from(Scalar::is_canonical(&l_minus_1)));
+    assert_eq!(Scalar::try_from_canonical_bytes(&l_minus_1).unwrap(), -Scalar::ONE);
+    assert!(!bool::from(Scalar::is_canonical(&hex_32(ED25519_ORDER))));
+    assert!(bool::from(Scalar::try_from_canonical_bytes(&hex_32(ED25519_ORDER)).is_none()));
+    assert!(bool::from(Scalar::try_from_canonical_bytes(&[0xFF; 32]).is_none()));
+    assert_eq!(Scalar::from_bytes_mod_order(&hex_32(ED25519_ORDER)), Scalar::ZERO);
+
+    // The base point has order l, and scalar multiplication respects the ring structure
+    let a = Scalar::from_bytes_mod_order(&rng.gen());
+    let b = Scalar::from_bytes_mod_order(&rng.gen());
+    assert!((ED25519_BASEPOINT * &-Scalar::ONE + ED25519_BASEPOINT).is_identity());
+    assert_eq!((ED25519_BASEPOINT * &a) * &b, ED25519_BASEPOINT * &(a * b));
+    assert_eq!(ED25519_BASEPOINT * &a + ED25519_BASEPOINT * &b, ED25519_BASEPOINT * &(a + b));
+}
+
+// RFC 8032 section 7.1: (secret key, public key, message, signature)
+const ED25519_VECTORS: [(&str, &str, &str, &str); 5] = [
+    // TEST 1
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    ),
+    // TEST 2
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    ),
+    // TEST 3
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    ),
+    // TEST 1024
+    (
+        "f5e5767cf153319517630f226876b86c8160cc583bc013744c6bf255f5cc0ee5",
+        "278117fc144c72340f67d0f2316e8386ceffbf2b2428c9c51fef7c597f1d426e",
+        "08b8b2b733424243760fe426a4b54908632110a66c2f6591eabd3345e3e4eb98fa6e264bf09efe12ee50f8f54e9f77b1e355f6c50544e23fb1433ddf73be84d8\
+             79de7c0046dc4996d9e773f4bc9efe5738829adb26c81b37c93a1b270b20329d658675fc6ea534e0810a4432826bf58c941efb65d57a338bbd2e26640f89ffbc\
+             1a858efcb8550ee3a5e1998bd177e93a7363c344fe6b199ee5d02e82d522c4feba15452f80288a821a579116ec6dad2b3b310da903401aa62100ab5d1a36553e\
+             06203b33890cc9b832f79ef80560ccb9a39ce767967ed628c6ad573cb116dbefefd75499da96bd68a8a97b928a8bbc103b6621fcde2beca1231d206be6cd9ec7\
+             aff6f6c94fcd7204ed3455c68c83f4a41da4af2b74ef5c53f1d8ac70bdcb7ed185ce81bd84359d44254d95629e9855a94a7c1958d1f8ada5d0532ed8a5aa3fb2\
+             d17ba70eb6248e594e1a2297acbbb39d502f1a8c6eb6f1ce22b3de1a1f40cc24554119a831a9aad6079cad88425de6bde1a9187ebb6092cf67bf2b13fd65f270\
+             88d78b7e883c8759d2c4f5c65adb7553878ad575f9fad878e80a0c9ba63bcbcc2732e69485bbc9c90bfbd62481d9089beccf80cfe2df16a2cf65bd92dd597b07\
+             07e0917af48bbb75fed413d238f5555a7a569d80c3414a8d0859dc65a46128bab27af87a71314f318c782b23ebfe808b82b0ce26401d2e22f04d83d1255dc51a\
+             ddd3b75a2b1ae0784504df543af8969be3ea7082ff7fc9888c144da2af58429ec96031dbcad3dad9af0dcbaaaf268cb8fcffead94f3c7ca495e056a9b47acdb7\
+             51fb73e666c6c655ade8297297d07ad1ba5e43f1bca32301651339e22904cc8c42f58c30c04aafdb038dda0847dd988dcda6f3bfd15c4b4c4525004aa06eeff8\
+             ca61783aacec57fb3d1f92b0fe2fd1a85f6724517b65e614ad6808d6f6ee34dff7310fdc82aebfd904b01e1dc54b2927094b2db68d6f903b68401adebf5a7e08\
+             d78ff4ef5d63653a65040cf9bfd4aca7984a74d37145986780fc0b16ac451649de6188a7dbdf191f64b5fc5e2ab47b57f7f7276cd419c17a3ca8e1b939ae49e4\
+             88acba6b965610b5480109c8b17b80e1b7b750dfc7598d5d5011fd2dcc5600a32ef5b52a1ecc820e308aa342721aac0943bf6686b64b2579376504ccc493d97e\
+             6aed3fb0f9cd71a43dd497f01f17c0e2cb3797aa2a2f256656168e6c496afc5fb93246f6b1116398a346f1a641f3b041e989f7914f90cc2c7fff357876e506b5\
+             0d334ba77c225bc307ba537152f3f1610e4eafe595f6d9d90d11faa933a15ef1369546868a7f3a45a96768d40fd9d03412c091c6315cf4fde7cb68606937380d\
+             b2eaaa707b4c4185c32eddcdd306705e4dc1ffc872eeee475a64dfac86aba41c0618983f8741c5ef68d3a101e8a3b8cac60c905c15fc910840b94c00a0b9d0",
+        "0aab4c900501b3e24d7cdf4663326a3a87df5e4843b2cbdb67cbf6e460fec350aa5371b1508f9f4528ecea23c436d94b5e8fcd4f681e30a6ac00a9704a188a03",
+    ),
+    // TEST SHA(abc)
+    (
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+    ),
+];
+
+#[test]
+fn ed25519_rfc8032() {
+    for (secret, public, message, signature) in ED25519_VECTORS.iter() {
+        let signing_key = SigningKey::from_bytes(&hex_32(secret));
+        let verifying_key = signing_key.verifying_key();
+        assert_eq!(verifying_key.to_bytes(), hex_32(public));
+        let signature_bytes = hex_bytes(signature);
+        let signature = signing_key.sign(&hex_bytes(message));
+        assert_eq!(&signature.to_bytes()[..], &signature_bytes[..]);
+        assert_eq!(VerifyingKey::from_bytes(&hex_32(public)).unwrap().verify(&hex_bytes(message), &signature), Ok(()));
+    }
+}
+
+#[test]
+fn ed25519_rejects() {
+    let mut rng = rand::thread_rng();
+    let signing_key = SigningKey::random_from_rng(&mut rng);
+    let verifying_key = signing_key.verifying_key();
+    let signature = signing_key.sign(b"message");
+    assert_eq!(signing_key.verify(b"message", &signature), Ok(()));
+    assert_eq!(verifying_key.verify(b"massage", &signature), Err(SignatureError::VerificationFailed));
+
+    // S + l encodes the same residue, so only the canonical check stands between it and a second valid signature
+    let mut bytes = signature.to_bytes();
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&bytes[32..]);
+    let s_plus_l = BigUint::from_bytes_le(&s) + BigUint::from_bytes_le(&hex_bytes(ED25519_ORDER));
+    bytes[32..].copy_from_slice(&s_plus_l.to_bytes_le()[..32]);
+    assert_eq!(verifying_key.verify(b"message", &Signature::from_bytes(&bytes)), Err(SignatureError::NonCanonicalS));
+
+    // y = 2 is not on the curve, neither as R nor as A
+    let off_curve = hex_32("0200000000000000000000000000000000000000000000000000000000000000");
+    bytes = signature.to_bytes();
+    bytes[..32].copy_from_slice(&off_curve);
+    assert_eq!(verifying_key.verify(b"message", &Signature::from_bytes(&bytes)), Err(SignatureError::MalformedR));
+    assert_eq!(VerifyingKey::from_bytes(&off_curve), Err(SignatureError::MalformedPublicKey));
+}
+
+// RFC 8032 section 7.2: (secret key, public key, message, context, signature)
+const ED25519CTX_VECTORS: [(&str, &str, &str, &str, &str); 4] = [
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+        "f726936d19c800494e3fdaff20b276a8",
+        "666f6f",
+        "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+    ),
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+        "f726936d19c800494e3fdaff20b276a8",
+        "626172",
+        "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+    ),
+    (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+        "f726936d19c800494e3fdaff20b276a9",
+        "666f6f",
+        "95b5cd32e8594990a54e2010c46ffc7bf49c466a00cdbec9644681feef859ae98fe139d9e7b9130a6632614adca2fabd0a4eb4f3175b91af633b40500f34c60e",
+    ),
+    (
+        "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+        "0f1d1274943b91415889152e893d80e93275a1fc0b65fd71b4b0dda10ad7d772",
+        "f726936d19c800494e3fdaff20b276a8",
+        "666f6f",
+        "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+    ),
+];
+
+#[test]
+fn ed25519ctx_rfc8032() {
+    for (secret, public, message, context, signature) in ED25519CTX_VECTORS.iter() {
+        let signing_key = SigningKey::from_bytes(&hex_32(secret));
+        assert_eq!(signing_key.verifying_key().to_bytes(), hex_32(public));
+        let signature_bytes = hex_bytes(signature);
+        let signature = signing_key.sign_ctx(&hex_bytes(message), &hex_bytes(context)).unwrap();
+        assert_eq!(&signature.to_bytes()[..], &signature_bytes[..]);
+        let verifying_key = signing_key.verifying_key();
+        assert_eq!(verifying_key.verify_ctx(&hex_bytes(message), &hex_bytes(context), &signature), Ok(()));
+        // The context is bound into the signature
+        assert_eq!(verifying_key.verify_ctx(&hex_bytes(message), b"baz", &signature), Err(SignatureError::VerificationFailed));
+        assert_eq!(verifying_key.verify(&hex_bytes(message), &signature), Err(SignatureError::VerificationFailed));
+    }
+    let signing_key = SigningKey::from_bytes(&hex_32(ED25519CTX_VECTORS[0].0));
+    assert_eq!(signing_key.sign_ctx(b"message", b"").err(), Some(SignatureError::InvalidContext(0)));
+    assert_eq!(signing_key.sign_ctx(b"message", &[0u8; 256]).err(), Some(SignatureError::InvalidContext(256)));
+}
+
+#[test]
+fn ed25519ph_rfc8032() {
+    // RFC 8032 section 7.3: the message "abc" with an empty context
+    let signing_key = SigningKey::from_bytes(&hex_32("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42"));
+    assert_eq!(signing_key.verifying_key().to_bytes(), hex_32("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"));
+    let prehash = ed25519ph_prehash(b"abc");
+    assert_eq!(prehash, <[u8; 64]>::from(Sha512::digest(b"abc")));
+    let signature = signing_key.sign_prehashed(&prehash, b"").unwrap();
+    assert_eq!(
+        &signature.to_bytes()[..],
+        &hex_bytes("98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406")[..]
+    );
+    let verifying_key = signing_key.verifying_key();
+    assert_eq!(verifying_key.verify_prehashed(&prehash, b"", &signature), Ok(()));
+    assert_eq!(verifying_key.verify_prehashed(&prehash, b"foo", &signature), Err(SignatureError::VerificationFailed));
+    assert_eq!(verifying_key.verify(&prehash, &signature), Err(SignatureError::VerificationFailed));
+    let with_context = signing_key.sign_prehashed(&prehash, b"foo").unwrap();
+    assert_eq!(verifying_key.verify_prehashed(&prehash, b"foo", &with_context), Ok(()));
+}
+
+const ED448_ORDER: &str = "f34458ab92c27823558fc58d72c26c219036d6ae49db4ec4e923ca7cffffffffffffffffffffffffffffffffffffffffffffffffffffff3f00";
+
+fn hex_57(hex_str: &str) -> [u8; 57] {
+    let mut result = [0u8; 57];
+    result.copy_from_slice(&hex_bytes(hex_str));
+    result
+}
+
+#[test]
+fn fuzz_scalar448() {
+    let order = BigUint::from_bytes_le(&hex_bytes(ED448_ORDER));
+    let mut rng = rand::thread_rng();
+    for _index in 0..2_000 {
+        let mut wide1 = [0u8; 114];
+        let mut wide2 = [0u8; 114];
+        rng.fill(&mut wide1[..]);
+        rng.fill(&mut wide2[..]);
+        let a = Scalar448::from_bytes_mod_order_wide(&wide1);
+        let b = Scalar448::from_bytes_mod_order_wide(&wide2);
+        let big_a = BigUint::from_bytes_le(&wide1) % &order;
+        let big_b = BigUint::from_bytes_le(&wide2) % &order;
+        let big = |scalar: Scalar448| BigUint::from_bytes_le(&scalar.to_bytes());
+        assert_eq!(big(a), big_a);
+        assert_eq!(big(a + b), (&big_a + &big_b) % &order);
+        assert_eq!(big(a - b), (&big_a + &order - &big_b) % &order);
+        assert_eq!(big(a * b), (&big_a * &big_b) % &order);
+        assert_eq!(big(-a), (&order - &big_a) % &order);
+        let mut narrow = [0u8; 57];
+        narrow.copy_from_slice(&wide1[..57]);
+        assert_eq!(big(Scalar448::from_bytes_mod_order(&narrow)), BigUint::from_bytes_le(&narrow) % &order);
+        assert_eq!(Scalar448::try_from_canonical_bytes(&a.to_bytes()).unwrap(), a);
+    }
+    assert_eq!(Scalar448::from(2) * Scalar448::from(3), Scalar448::from(6));
+
+    let mut l_minus_1 = hex_57(ED448_ORDER);
+    l_minus_1[0] -= 1;
+    assert_eq!(Scalar448::try_from_canonical_bytes(&l_minus_1).unwrap(), -Scalar448::ONE);
+    assert!(bool::from(Scalar448::try_from_canonical_bytes(&hex_57(ED448_ORDER)).is_none()));
+    assert!(bool::from(Scalar448::try_from_canonical_bytes(&[0xFF; 57]).is_none()));
+    assert_eq!(Scalar448::from_bytes_mod_order(&hex_57(ED448_ORDER)), Scalar448::ZERO);
+
+    // The base point has order l, and scalar multiplication respects the ring structure
+    let mut seed = [0u8; 57];
+    rng.fill(&mut seed[..]);
+    let a = Scalar448::from_bytes_mod_order(&seed);
+    rng.fill(&mut seed[..]);
+    let b = Scalar448::from_bytes_mod_order(&seed);
+    assert!((ED448_BASEPOINT * &-Scalar448::ONE + ED448_BASEPOINT).is_identity());
+    assert_eq!((ED448_BASEPOINT * &a) * &b, ED448_BASEPOINT * &(a * b));
+    assert_eq!(ED448_BASEPOINT * &a + ED448_BASEPOINT * &b, ED448_BASEPOINT * &(a + b));
+    assert_eq!(ED448_BASEPOINT.double(), ED448_BASEPOINT + ED448_BASEPOINT);
+    let encoded = (ED448_BASEPOINT * &a).compress();
+    assert_eq!(Edwards448Point::decompress(&encoded).unwrap(), ED448_BASEPOINT * &a);
+}
+
+// RFC 8032 section 7.4: (secret key, public key, message, context, signature)
+const ED448_VECTORS: [(&str, &str, &str, &str, &str); 9] = [
+    // Blank
+    (
+        "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+        "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+        "",
+        "",
+        "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a\
+             9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600",
+    ),
+    // 1 octet
+    (
+        "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+        "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+        "03",
+        "",
+        "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cb\
+             cee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00",
+    ),
+    // 1 octet (with context)
+    (
+        "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+        "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+        "03",
+        "666f6f",
+        "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b32a89f7d2151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea000c85741de5c8da\
+             1144a6a1aba7f96de42505d7a7298524fda538fccbbb754f578c1cad10d54d0d5428407e85dcbc98a49155c13764e66c3c00",
+    ),
+    // 11 octets
+    (
+        "cd23d24f714274e744343237b93290f511f6425f98e64459ff203e8985083ffdf60500553abc0e05cd02184bdb89c4ccd67e187951267eb328",
+        "dcea9e78f35a1bf3499a831b10b86c90aac01cd84b67a0109b55a36e9328b1e365fce161d71ce7131a543ea4cb5f7e9f1d8b00696447001400",
+        "0c3e544074ec63b0265e0c",
+        "",
+        "1f0a8888ce25e8d458a21130879b840a9089d999aaba039eaf3e3afa090a09d389dba82c4ff2ae8ac5cdfb7c55e94d5d961a29fe0109941e00b8dbdeea6d3b05\
+             1068df7254c0cdc129cbe62db2dc957dbb47b51fd3f213fb8698f064774250a5028961c9bf8ffd973fe5d5c206492b140e00",
+    ),
+    // 12 octets
+    (
+        "258cdd4ada32ed9c9ff54e63756ae582fb8fab2ac721f2c8e676a72768513d939f63dddb55609133f29adf86ec9929dccb52c1c5fd2ff7e21b",
+        "3ba16da0c6f2cc1f30187740756f5e798d6bc5fc015d7c63cc9510ee3fd44adc24d8e968b6e46e6f94d19b945361726bd75e149ef09817f580",
+        "64a65f3cdedcdd66811e2915",
+        "",
+        "7eeeab7c4e50fb799b418ee5e3197ff6bf15d43a14c34389b59dd1a7b1b85b4ae90438aca634bea45e3a2695f1270f07fdcdf7c62b8efeaf00b45c2c96ba457e\
+             b1a8bf075a3db28e5c24f6b923ed4ad747c3c9e03c7079efb87cb110d3a99861e72003cbae6d6b8b827e4e6c143064ff3c00",
+    ),
+    // 13 octets
+    (
+        "7ef4e84544236752fbb56b8f31a23a10e42814f5f55ca037cdcc11c64c9a3b2949c1bb60700314611732a6c2fea98eebc0266a11a93970100e",
+        "b3da079b0aa493a5772029f0467baebee5a8112d9d3a22532361da294f7bb3815c5dc59e176b4d9f381ca0938e13c6c07b174be65dfa578e80",
+        "64a65f3cdedcdd66811e2915e7",
+        "",
+        "6a12066f55331b6c22acd5d5bfc5d71228fbda80ae8dec26bdd306743c5027cb4890810c162c027468675ecf645a83176c0d7323a2ccde2d80efe5a1268e8aca\
+             1d6fbc194d3f77c44986eb4ab4177919ad8bec33eb47bbb5fc6e28196fd1caf56b4e7e0ba5519234d047155ac727a1053100",
+    ),
+    // 64 octets
+    (
+        "d65df341ad13e008567688baedda8e9dcdc17dc024974ea5b4227b6530e339bff21f99e68ca6968f3cca6dfe0fb9f4fab4fa135d5542ea3f01",
+        "df9705f58edbab802c7f8363cfe5560ab1c6132c20a9f1dd163483a26f8ac53a39d6808bf4a1dfbd261b099bb03b3fb50906cb28bd8a081f00",
+        "bd0f6a3747cd561bdddf4640a332461a4a30a12a434cd0bf40d766d9c6d458e5512204a30c17d1f50b5079631f64eb3112182da3005835461113718d1a5ef944",
+        "",
+        "554bc2480860b49eab8532d2a533b7d578ef473eeb58c98bb2d0e1ce488a98b18dfde9b9b90775e67f47d4a1c3482058efc9f40d2ca033a0801b63d45b3b722e\
+             f552bad3b4ccb667da350192b61c508cf7b6b5adadc2c8d9a446ef003fb05cba5f30e88e36ec2703b349ca229c2670833900",
+    ),
+    // 256 octets
+    (
+        "2ec5fe3c17045abdb136a5e6a913e32ab75ae68b53d2fc149b77e504132d37569b7e766ba74a19bd6162343a21c8590aa9cebca9014c636df5",
+        "79756f014dcfe2079f5dd9e718be4171e2ef2486a08f25186f6bff43a9936b9bfe12402b08ae65798a3d81e22e9ec80e7690862ef3d4ed3a00",
+        "15777532b0bdd0d1389f636c5f6b9ba734c90af572877e2d272dd078aa1e567cfa80e12928bb542330e8409f3174504107ecd5efac61ae7504dabe2a602ede89\
+             e5cca6257a7c77e27a702b3ae39fc769fc54f2395ae6a1178cab4738e543072fc1c177fe71e92e25bf03e4ecb72f47b64d0465aaea4c7fad372536c8ba516a60\
+             39c3c2a39f0e4d832be432dfa9a706a6e5c7e19f397964ca4258002f7c0541b590316dbc5622b6b2a6fe7a4abffd96105eca76ea7b98816af0748c10df048ce0\
+             12d901015a51f189f3888145c03650aa23ce894c3bd889e030d565071c59f409a9981b51878fd6fc110624dcbcde0bf7a69ccce38fabdf86f3bef6044819de11",
+        "",
+        "c650ddbb0601c19ca11439e1640dd931f43c518ea5bea70d3dcde5f4191fe53f00cf966546b72bcc7d58be2b9badef28743954e3a44a23f880e8d4f1cfce2d7a\
+             61452d26da05896f0a50da66a239a8a188b6d825b3305ad77b73fbac0836ecc60987fd08527c1a8e80d5823e65cafe2a3d00",
+    ),
+    // 1023 octets
+    (
+        "872d093780f5d3730df7c212664b37b8a0f24f56810daa8382cd4fa3f77634ec44dc54f1c2ed9bea86fafb7632d8be199ea165f5ad55dd9ce8",
+        "a81b2e8a70a5ac94ffdbcc9badfc3feb0801f258578bb114ad44ece1ec0e799da08effb81c5d685c0c56f64eecaef8cdf11cc38737838cf400",
+        "6ddf802e1aae4986935f7f981ba3f0351d6273c0a0c22c9c0e8339168e675412a3debfaf435ed651558007db4384b650fcc07e3b586a27a4f7a00ac8a6fec2cd\
+             86ae4bf1570c41e6a40c931db27b2faa15a8cedd52cff7362c4e6e23daec0fbc3a79b6806e316efcc7b68119bf46bc76a26067a53f296dafdbdc11c77f7777e9\
+             72660cf4b6a9b369a6665f02e0cc9b6edfad136b4fabe723d2813db3136cfde9b6d044322fee2947952e031b73ab5c603349b307bdc27bc6cb8b8bbd7bd32321\
+             9b8033a581b59eadebb09b3c4f3d2277d4f0343624acc817804728b25ab797172b4c5c21a22f9c7839d64300232eb66e53f31c723fa37fe387c7d3e50bdf9813\
+             a30e5bb12cf4cd930c40cfb4e1fc622592a49588794494d56d24ea4b40c89fc0596cc9ebb961c8cb10adde976a5d602b1c3f85b9b9a001ed3c6a4d3b1437f520\
+             96cd1956d042a597d561a596ecd3d1735a8d570ea0ec27225a2c4aaff26306d1526c1af3ca6d9cf5a2c98f47e1c46db9a33234cfd4d81f2c98538a09ebe76998\
+             d0d8fd25997c7d255c6d66ece6fa56f11144950f027795e653008f4bd7ca2dee85d8e90f3dc315130ce2a00375a318c7c3d97be2c8ce5b6db41a6254ff264fa6\
+             155baee3b0773c0f497c573f19bb4f4240281f0b1f4f7be857a4e59d416c06b4c50fa09e1810ddc6b1467baeac5a3668d11b6ecaa901440016f389f80acc4db9\
+             77025e7f5924388c7e340a732e554440e76570f8dd71b7d640b3450d1fd5f0410a18f9a3494f707c717b79b4bf75c98400b096b21653b5d217cf3565c9597456\
+             f70703497a078763829bc01bb1cbc8fa04eadc9a6e3f6699587a9e75c94e5bab0036e0b2e711392cff0047d0d6b05bd2a588bc109718954259f1d86678a579a3\
+             120f19cfb2963f177aeb70f2d4844826262e51b80271272068ef5b3856fa8535aa2a88b2d41f2a0e2fda7624c2850272ac4a2f561f8f2f7a318bfd5caf969614\
+             9e4ac824ad3460538fdc25421beec2cc6818162d06bbed0c40a387192349db67a118bada6cd5ab0140ee273204f628aad1c135f770279a651e24d8c14d75a605\
+             9d76b96a6fd857def5e0b354b27ab937a5815d16b5fae407ff18222c6d1ed263be68c95f32d908bd895cd76207ae726487567f9a67dad79abec316f683b17f2d\
+             02bf07e0ac8b5bc6162cf94697b3c27cd1fea49b27f23ba2901871962506520c392da8b6ad0d99f7013fbc06c2c17a569500c8a7696481c1cd33e9b14e40b82e\
+             79a5f5db82571ba97bae3ad3e0479515bb0e2b0f3bfcd1fd33034efc6245eddd7ee2086ddae2600d8ca73e214e8c2b0bdb2b047c6a464a562ed77b73d2d841c4\
+             b34973551257713b753632efba348169abc90a68f42611a40126d7cb21b58695568186f7e569d2ff0f9e745d0487dd2eb997cafc5abf9dd102e62ff66cba87",
+        "",
+        "e301345a41a39a4d72fff8df69c98075a0cc082b802fc9b2b6bc503f926b65bddf7f4c8f1cb49f6396afc8a70abe6d8aef0db478d4c6b2970076c6a0484fe76d\
+             76b3a97625d79f1ce240e7c576750d295528286f719b413de9ada3e8eb78ed573603ce30d8bb761785dc30dbc320869e1a00",
+    ),
+];
+
+#[test]
+fn ed448_rfc8032() {
+    for (secret, public, message, context, signature) in ED448_VECTORS.iter() {
+        let signing_key = Ed448SigningKey::from_bytes(&hex_57(secret));
+        assert_eq!(signing_key.verifying_key().to_bytes(), hex_57(public));
+        let signature_bytes = hex_bytes(signature);
+        let signature = signing_key.sign_ctx(&hex_bytes(message), &hex_bytes(context)).unwrap();
+        assert_eq!(&signature.to_bytes()[..], &signature_bytes[..]);
+        let verifying_key = Ed448VerifyingKey::from_bytes(&hex_57(public)).unwrap();
+        assert_eq!(verifying_key.verify_ctx(&hex_bytes(message), &hex_bytes(context), &signature), Ok(()));
+        if context.is_empty() {
+            assert_eq!(signing_key.sign(&hex_bytes(message)), signature);
+            assert_eq!(verifying_key.verify(&hex_bytes(message), &signature), Ok(()));
+        } else {
+            assert_eq!(verifying_key.verify(&hex_bytes(message), &signature), Err(SignatureError::VerificationFailed));
+        }
+    }
+}
+
+#[test]
+fn ed448ph_rfc8032() {
+    // RFC 8032 section 7.5: the message "abc", without and with the context "foo"
+    let signing_key = Ed448SigningKey::from_bytes(&hex_57(
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+    ));
+    let verifying_key = signing_key.verifying_key();
+    assert_eq!(
+        verifying_key.to_bytes(),
+        hex_57("259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880")
+    );
+    let prehash = ed448ph_prehash(b"abc");
+    let expected = [
+        (
+            "",
+            "822f6901f7480f3d5f562c592994d9693602875614483256505600bbc281ae381f54d6bce2ea911574932f52a4e6cadd78769375ec3ffd1b801a0d9b3f4030cd\
+                 433964b6457ea39476511214f97469b57dd32dbc560a9a94d00bff07620464a3ad203df7dc7ce360c3cd3696d9d9fab90f00",
+        ),
+        (
+            "666f6f",
+            "c32299d46ec8ff02b54540982814dce9a05812f81962b649d528095916a2aa481065b1580423ef927ecf0af5888f90da0f6a9a85ad5dc3f280d91224ba9911a3\
+                 653d00e484e2ce232521481c8658df304bb7745a73514cdb9bf3e15784ab71284f8d0704a608c54a6b62d97beb511d132100",
+        ),
+    ];
+    for (context, signature_hex) in expected.iter() {
+        let signature = signing_key.sign_prehashed(&prehash, &hex_bytes(context)).unwrap();
+        assert_eq!(&signature.to_bytes()[..], &hex_bytes(signature_hex)[..]);
+        assert_eq!(verifying_key.verify_prehashed(&prehash, &hex_bytes(context), &signature), Ok(()));
+        assert_eq!(verifying_key.verify_prehashed(&prehash, b"bar", &signature), Err(SignatureError::VerificationFailed));
+        assert_eq!(verifying_key.verify_ctx(&prehash, &hex_bytes(context), &signature), Err(SignatureError::VerificationFailed));
+    }
+}
+
+#[test]
+fn ed448_rejects() {
+    let mut rng = rand::thread_rng();
+    let signing_key = Ed448SigningKey::random_from_rng(&mut rng);
+    let verifying_key = signing_key.verifying_key();
+    let signature = signing_key.sign(b"message");
+    assert_eq!(signing_key.verify(b"message", &signature), Ok(()));
+    assert_eq!(verifying_key.verify(b"massage", &signature), Err(SignatureError::VerificationFailed));
+
+    // S + l encodes the same residue, so only the canonical check stands between it and a second valid signature
+    let mut bytes = signature.to_bytes();
+    let s_plus_l = BigUint::from_bytes_le(&bytes[57..]) + BigUint::from_bytes_le(&hex_bytes(ED448_ORDER));
+    bytes[57..57 + s_plus_l.to_bytes_le().len()].copy_from_slice(&s_plus_l.to_bytes_le());
+    assert_eq!(verifying_key.verify(b"message", &Ed448Signature::from_bytes(&bytes)), Err(SignatureError::NonCanonicalS));
+
+    // y = 2 is not on the curve, neither as R nor as A; nor may the last byte hold more than the sign
+    let mut off_curve = [0u8; 57];
+    off_curve[0] = 2;
+    bytes = signature.to_bytes();
+    bytes[..57].copy_from_slice(&off_curve);
+    assert_eq!(verifying_key.verify(b"message", &Ed448Signature::from_bytes(&bytes)), Err(SignatureError::MalformedR));
+    assert_eq!(Ed448VerifyingKey::from_bytes(&off_curve), Err(SignatureError::MalformedPublicKey));
+    let mut stray_bits = verifying_key.to_bytes();
+    stray_bits[56] |= 1;
+    assert_eq!(Ed448VerifyingKey::from_bytes(&stray_bits), Err(SignatureError::MalformedPublicKey));
+
+    assert_eq!(signing_key.sign_ctx(b"message", &[0u8; 256]).err(), Some(SignatureError::InvalidContext(256)));
+    assert_eq!(verifying_key.verify_ctx(b"message", &[0u8; 256], &signature), Err(SignatureError::InvalidContext(256)));
+}
+
+#[test]
+fn ed25519_batch() {
+    let mut rng = rand::thread_rng();
+    let messages: Vec<Vec<u8>> = (0..16).map(|index| format!("log line {}", index).into_bytes()).collect();
+    let signing_keys: Vec<SigningKey> = (0..16).map(|_index| SigningKey::random_from_rng(&mut rng)).collect();
+    let verifying_keys: Vec<VerifyingKey> = signing_keys.iter().map(SigningKey::verifying_key).collect();
+    let mut signatures: Vec<Signature> = signing_keys.iter().zip(&messages).map(|(key, message)| key.sign(message)).collect();
+    let mut message_refs: Vec<&[u8]> = messages.iter().map(|message| &message[..]).collect();
+    assert_eq!(verify_batch(&message_refs, &signatures, &verifying_keys, &mut rng), Ok(()));
+    assert_eq!(verify_batch(&[], &[], &[], &mut rng), Ok(()));
+
+    // A wrong message and a malformed R are both singled out
+    message_refs[3] = b"forged";
+    let mut bytes = signatures[11].to_bytes();
+    bytes[..32].copy_from_slice(&hex_32("0200000000000000000000000000000000000000000000000000000000000000"));
+    signatures[11] = Signature::from_bytes(&bytes);
+    assert_eq!(verify_batch(&message_refs, &signatures, &verifying_keys, &mut rng), Err(vec![3, 11]));
+
+    // The RFC 8032 section 7.1 vectors also verify as a batch
+    let vectors: Vec<(VerifyingKey, Vec<u8>, Signature)> = ED25519_VECTORS
+        .iter()
+        .map(|(_secret, public, message, signature)| {
+            let mut signature_bytes = [0u8; 64];
+            signature_bytes.copy_from_slice(&hex_bytes(signature));
+            (VerifyingKey::from_bytes(&hex_32(public)).unwrap(), hex_bytes(message), Signature::from_bytes(&signature_bytes))
+        })
+        .collect();
+    let keys: Vec<VerifyingKey> = vectors.iter().map(|vector| vector.0).collect();
+    let messages: Vec<&[u8]> = vectors.iter().map(|vector| &vector.1[..]).collect();
+    let signatures: Vec<Signature> = vectors.iter().map(|vector| vector.2).collect();
+    assert_eq!(verify_batch(&messages, &signatures, &keys, &mut rng), Ok(()));
+}
+
+#[test]
+fn multiscalar_mul() {
+    // Points [b_i] B with known b_i, so the expected sum is [sum a_i b_i] B
+    let mut rng = rand::thread_rng();
+    let mut extremes = vec![Scalar::ZERO, Scalar::ONE, -Scalar::ONE, Scalar::from(8), -Scalar::from(8), Scalar::from(u64::MAX)];
+    extremes.push(Scalar::try_from_canonical_bytes(&hex_32("8888888888888888888888888888888888888888888888888888888888888808")).unwrap());
+    for &n in [0, 1, 2, 7, 64, 190, 600, 900].iter() {
+        let mut scalars: Vec<Scalar> = (0..n).map(|_| Scalar::from_bytes_mod_order(&rng.gen())).collect();
+        let logs: Vec<Scalar> = (0..n).map(|_| Scalar::from_bytes_mod_order(&rng.gen())).collect();
+        for (scalar, extreme) in scalars.iter_mut().zip(extremes.iter()) {
+            *scalar = *extreme;
+        }
+        let points: Vec<EdwardsPoint> = logs.iter().map(EdwardsPoint::mul_base).collect();
+        let expected = scalars.iter().zip(logs.iter()).fold(Scalar::ZERO, |sum, (a, b)| sum + a * b);
+        let expected = EdwardsPoint::mul_base(&expected);
+        assert_eq!(EdwardsPoint::vartime_multiscalar_mul(&scalars, &points), expected);
+        if n <= 64 {
+            assert_eq!(EdwardsPoint::multiscalar_mul(&scalars, &points), expected);
+        }
+    }
+}
+
+// RFC 9496 appendix A.1: encodings of [i]B for i = 0..15
+const RISTRETTO_MULTIPLES: [&str; 16] = [
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+    "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+    "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+    "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+    "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+    "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+    "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+    "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+    "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+    "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+    "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+    "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+    "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+    "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+    "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e",
+];
+
+// RFC 9496 appendix A.2: non-canonical, negative, non-square, negative xy and s = -1 encodings
+const RISTRETTO_BAD_ENCODINGS: [&str; 30] = [
+    "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "0100000000000000000000000000000000000000000000000000000000000080",
+    "0100000000000000000000000000000000000000000000000000000000000000",
+    "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+    "c34c4e1826e5d403b78e246e88aa051c36ccf0aafebffe137d148a2bf9104562",
+    "c940e5a4404157cfb1628b108db051a8d439e1a421394ec4ebccb9ec92a8ac78",
+    "47cfc5497c53dc8e61c91d17fd626ffb1c49e2bca94eed052281b510b1117a24",
+    "f1c6165d33367351b0da8f6e4511010c68174a03b6581212c71c0e1d026c3c72",
+    "87260f7a2f12495118360f02c26a470f450dadf34a413d21042b43b9d93e1309",
+    "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+    "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+    "de6a7b00deadc788eb6b6c8d20c0ae96c2f2019078fa604fee5b87d6e989ad7b",
+    "bcab477be20861e01e4a0e295284146a510150d9817763caf1a6f4b422d67042",
+    "2a292df7e32cababbd9de088d1d1abec9fc0440f637ed2fba145094dc14bea08",
+    "f4a9e534fc0d216c44b218fa0c42d99635a0127ee2e53c712f70609649fdff22",
+    "8268436f8c4126196cf64b3c7ddbda90746a378625f9813dd9b8457077256731",
+    "2810e5cbc2cc4d4eece54f61c6f69758e289aa7ab440b3cbeaa21995c2f4232b",
+    "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+    "a45fdc55c76448c049a1ab33f17023edfb2be3581e9c7aade8a6125215e04220",
+    "d483fe813c6ba647ebbfd3ec41adca1c6130c2beeee9d9bf065c8d151c5f396e",
+    "8a2e1d30050198c65a54483123960ccc38aef6848e1ec8f5f780e8523769ba32",
+    "32888462f8b486c68ad7dd9610be5192bbeaf3b443951ac1a8118419d9fa097b",
+    "227142501b9d4355ccba290404bde41575b037693cef1f438c47f8fbf35d1165",
+    "5c37cc491da847cfeb9281d407efc41e15144c876e0170b499a96a22ed31e01e",
+    "445425117cb8c90edcbc7c1cc0e74f747f2c1efa5630a967c64f287792a48a4b",
+    "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+];
+
+// RFC 9496 appendix A.3: (64 uniform bytes, encoding of the derived element)
+const RISTRETTO_UNIFORM_VECTORS: [(&str, &str); 7] = [
+    (
+        "5d1be09e3d0c82fc538112490e35701979d99e06ca3e2b5b54bffe8b4dc772c14d98b696a1bbfb5ca32c436cc61c16563790306c79eaca7705668b47dffe5bb6",
+        "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46",
+    ),
+    (
+        "f116b34b8f17ceb56e8732a60d913dd10cce47a6d53bee9204be8b44f6678b270102a56902e2488c46120e9276cfe54638286b9e4b3cdb470b542d46c2068d38",
+        "f26e5b6f7d362d2d2a94c5d0e7602cb4773c95a2e5c31a64f133189fa76ed61b",
+    ),
+    (
+        "8422e1bbdaab52938b81fd602effb6f89110e1e57208ad12d9ad767e2e25510c27140775f9337088b982d83d7fcf0b2fa1edffe51952cbe7365e95c86eaf325c",
+        "006ccd2a9e6867e6a2c5cea83d3302cc9de128dd2a9a57dd8ee7b9d7ffe02826",
+    ),
+    (
+        "ac22415129b61427bf464e17baee8db65940c233b98afce8d17c57beeb7876c2150d15af1cb1fb824bbd14955f2b57d08d388aab431a391cfc33d5bafb5dbbaf",
+        "f8f0c87cf237953c5890aec3998169005dae3eca1fbb04548c635953c817f92a",
+    ),
+    (
+        "165d697a1ef3d5cf3c38565beefcf88c0f282b8e7dbd28544c483432f1cec7675debea8ebb4e5fe7d6f6e5db15f15587ac4d4d4a1de7191e0c1ca6664abcc413",
+        "ae81e7dedf20a497e10c304a765c1767a42d6e06029758d2d7e8ef7cc4c41179",
+    ),
+    (
+        "a836e6c9a9ca9f1e8d486273ad56a78c70cf18f0ce10abb1c7172ddd605d7fd2979854f47ae1ccf204a33102095b4200e5befc0465accc263175485f0e17ea5c",
+        "e2705652ff9f5e44d3e841bf1c251cf7dddb77d140870d1ab2ed64f1a9ce8628",
+    ),
+    (
+        "2cdc11eaeb95daf01189417cdddbf95952993aa9cb9c640eb5058d09702c74622c9965a697a3b345ec24ee56335b556e677b30e6f90ac77d781064f866a3c982",
+        "80bd07262511cdde4863f8a7434cef696750681cb9510eea557088f76d9e5065",
+    ),
+];
+
+#[test]
+fn ristretto_rfc9496() {
+    let mut point = RistrettoPoint::identity();
+    for encoding in RISTRETTO_MULTIPLES.iter() {
+        assert_eq!(point.compress(), hex_32(encoding));
+        assert_eq!(RistrettoPoint::decompress(&hex_32(encoding)), Some(point));
+        point += &RISTRETTO_BASEPOINT;
+    }
+    for encoding in RISTRETTO_BAD_ENCODINGS.iter() {
+        assert_eq!(RistrettoPoint::decompress(&hex_32(encoding)), None);
+    }
+    for (input, encoding) in RISTRETTO_UNIFORM_VECTORS.iter() {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&hex_bytes(input));
+        assert_eq!(RistrettoPoint::from_uniform_bytes(&bytes).compress(), hex_32(encoding));
+    }
+}
+
+#[test]
+fn ristretto_group() {
+    // Adding the 4-torsion point (sqrt(-1), 0) changes the representative but not the element
+    let mut torsion = EdwardsPoint::identity();
+    torsion.x = Fe25519::from_bytes(&hex_32("b0a00e4a271beec478e42fad0618432fa7d7fb3d99004d2b0bdfc14f8024832b"));
+    torsion.y = Fe25519::ZERO;
+    assert!(torsion.mul_by_pow_2(2).is_identity() && !torsion.double().is_identity());
+    let shifted = RistrettoPoint(RISTRETTO_BASEPOINT.0 + torsion);
+    assert_eq!(shifted, RISTRETTO_BASEPOINT);
+    assert_eq!(shifted.compress(), RISTRETTO_BASEPOINT.compress());
+
+    let mut rng = rand::thread_rng();
+    for _index in 0..8 {
+        let a = Scalar::from_bytes_mod_order(&rng.gen());
+        let b = Scalar::from_bytes_mod_order(&rng.gen());
+        let p = RISTRETTO_BASEPOINT * &a;
+        assert_eq!(RistrettoPoint::decompress(&p.compress()), Some(p));
+        assert_eq!(p * &b, RISTRETTO_BASEPOINT * &(a * b));
+        assert_eq!(p - p, RistrettoPoint::identity());
+        assert_eq!(-p + p, RistrettoPoint::identity());
+    }
+    assert_eq!(RISTRETTO_BASEPOINT * &-Scalar::ONE, -RISTRETTO_BASEPOINT);
+    let hashed = RistrettoPoint::hash_from_bytes(b"ristretto255");
+    assert_eq!(hashed, RistrettoPoint::hash_from_bytes(b"ristretto255"));
+    assert_ne!(hashed, RistrettoPoint::hash_from_bytes(b"ristretto256"));
+}
+
+#[test]
+fn elligator2_roundtrip() {
+    let a = Fe25519::from_bytes(&hex_32("066d070000000000000000000000000000000000000000000000000000000000"));
+    let mut rng = rand::thread_rng();
+    for _index in 0..200 {
+        let mut bytes: [u8; 32] = rng.gen();
+        bytes[31] &= 0x3F;
+        let r = Fe25519::from_bytes(&bytes);
+        let (u, v) = elligator2_map(&r);
+        assert_eq!(v.square(), u * (u.square() + a * u + Fe25519::ONE));
+        assert_eq!(elligator2_map(&-r), (u, v));
+        // The inverse recovers whichever of r and -r is below (p - 1) / 2
+        let r_inv = elligator2_inverse(&u, v.is_negative()).unwrap();
+        assert!(r_inv == r || r_inv == -r);
+        assert_eq!(r_inv.to_bytes()[31] & 0xC0, 0);
+        assert_eq!(elligator2_map(&r_inv), (u, v));
+    }
+
+    let (mut found, mut missing) = (0, 0);
+    for _index in 0..64 {
+        let public = PublicKey::from(&StaticSecret::random_from_rng(&mut rng));
+        let tweak: u8 = rng.gen();
+        match elligator2_representative(&public, tweak) {
+            Some(representative) => {
+                found += 1;
+                assert_eq!(representative[31] & 0xC0, tweak & 0xC0);
+                assert_eq!(elligator2_public_key(&representative), public);
+                assert_eq!(elligator2_public_key(&elligator2_representative(&public, tweak ^ 1).unwrap()), public);
+            }
+            None => missing += 1,
+        }
+    }
+    assert!(found > 0 && missing > 0);
+}
+
+#[test]
+fn elligator2_hidden_keys() {
+    let mut rng = rand::thread_rng();
+    let mut low_order_seen = false;
+    for _index in 0..16 {
+        let (secret, representative) = elligator2_keypair(&mut rng);
+        let public = elligator2_public_key(&representative);
+        low_order_seen |= public != PublicKey::from(&secret);
+        // The low-order component does not change the shared secret
+        let peer = StaticSecret::random_from_rng(&mut rng);
+        assert_eq!(peer.diffie_hellman(&public).to_bytes(), secret.diffie_hellman(&PublicKey::from(&peer)).to_bytes());
+    }
+    assert!(low_order_seen);
+}
+
+// The messages of the RFC 9380 appendix J vectors, with their DST prefix
+const H2C_DST_PREFIX: &str = "QUUX-V01-CS02-with-";
+
+fn h2c_messages() -> Vec<Vec<u8>> {
+    vec![b"".to_vec(), b"abc".to_vec(), b"abcdef0123456789".to_vec(), [&b"q128_"[..], &[b'q'; 128]].concat(), [&b"a512_"[..], &[b'a'; 512]].concat()]
+}
+
+// Big-endian hex, as the RFC prints field elements
+fn fe_from_be_hex(hex_str: &str) -> Fe25519 {
+    let mut bytes = hex_32(hex_str);
+    bytes.reverse();
+    Fe25519::from_bytes(&bytes)
+}
+
+// RFC 9380 appendix J.5.1 and J.5.2: hash_to_field outputs of the edwards25519 NU_ suite (u0) and RO_ suite (u0, u1)
+const H2C_FIELD_NU: [&str; 5] = [
+    "7f3e7fb9428103ad7f52db32f9df32505d7b427d894c5093f7a0f0374a30641d",
+    "09cfa30ad79bd59456594a0f5d3a76f6b71c6787b04de98be5cd201a556e253b",
+    "475ccff99225ef90d78cc9338e9f6a6bb7b17607c0c4428937de75d33edba941",
+    "049a1c8bd51bcb2aec339f387d1ff51428b88d0763a91bcdf6929814ac95d03d",
+    "3cb0178a8137cefa5b79a3a57c858d7eeeaa787b2781be4a362a2f0750d24fa0",
+];
+const H2C_FIELD_RO: [(&str, &str); 5] = [
+    ("03fef4813c8cb5f98c6eef88fae174e6e7d5380de2b007799ac7ee712d203f3a", "780bdddd137290c8f589dc687795aafae35f6b674668d92bf92ae793e6a60c75"),
+    ("5081955c4141e4e7d02ec0e36becffaa1934df4d7a270f70679c78f9bd57c227", "005bdc17a9b378b6272573a31b04361f21c371b256252ae5463119aa0b925b76"),
+    ("285ebaa3be701b79871bcb6e225ecc9b0b32dff2d60424b4c50642636a78d5b3", "2e253e6a0ef658fedb8e4bd6a62d1544fd6547922acb3598ec6b369760b81b31"),
+    ("4fedd25431c41f2a606952e2945ef5e3ac905a42cf64b8b4d4a83c533bf321af", "02f20716a5801b843987097a8276b6d869295b2e11253751ca72c109d37485a9"),
+    ("6e34e04a5106e9bd59f64aba49601bf09d23b27f7b594e56d5de06df4a4ea33b", "1c1c2cb59fc053f44b86c5d5eb8c1954b64976d0302d3729ff66e84068f5fd96"),
+];
+
+// Appendix J.4.1 and J.4.2: curve25519 (u, v)
+const H2C_CURVE25519_RO: [(&str, &str); 5] = [
+    ("2de3780abb67e861289f5749d16d3e217ffa722192d16bbd9d1bfb9d112b98c0", "3b5dc2a498941a1033d176567d457845637554a2fe7a3507d21abd1c1bd6e878"),
+    ("2b4419f1f2d48f5872de692b0aca72cc7b0a60915dd70bde432e826b6abc526d", "1b8235f255a268f0a6fa8763e97eb3d22d149343d495da1160eff9703f2d07dd"),
+    ("68ca1ea5a6acf4e9956daa101709b1eee6c1bb0df1de3b90d4602382a104c036", "2a375b656207123d10766e68b938b1812a4a6625ff83cb8d5e86f58a4be08353"),
+    ("096e9c8bae6c06b554c1ee69383bb0e82267e064236b3a30608d4ed20b73ac5a", "1eb5a62612cafb32b16c3329794645b5b948d9f8ffe501d4e26b073fef6de355"),
+    ("1bc61845a138e912f047b5e70ba9606ba2a447a4dade024c8ef3dd42b7bbc5fe", "623d05e47b70e25f7f1d51dda6d7c23c9a18ce015fe3548df596ea9e38c69bf1"),
+];
+const H2C_CURVE25519_NU: [(&str, &str); 5] = [
+    ("1bb913f0c9daefa0b3375378ffa534bda5526c97391952a7789eb976edfe4d08", "4548368f4f983243e747b62a600840ae7c1dab5c723991f85d3a9768479f3ec4"),
+    ("7c22950b7d900fa866334262fcaea47a441a578df43b894b4625c9b450f9a026", "5547bc00e4c09685dcbc6cb6765288b386d8bdcb595fa5a6e3969e08097f0541"),
+    ("31ad08a8b0deeb2a4d8b0206ca25f567ab4e042746f792f4b7973f3ae2096c52", "405070c28e78b4fa269427c82827261991b9718bd6c6e95d627d701a53c30db1"),
+    ("027877759d155b1997d0d84683a313eb78bdb493271d935b622900459d52ceaa", "54d691731a53baa30707f4a87121d5169fb5d587d70fb0292b5830dedbec4c18"),
+    ("5fd892c0958d1a75f54c3182a18d286efab784e774d1e017ba2fb252998b5dc1", "750af3c66101737423a4519ac792fb93337bd74ee751f19da4cf1e94f4d6d0b8"),
+];
+
+// Appendix J.5.1 and J.5.2: edwards25519 (x, y)
+const H2C_EDWARDS25519_RO: [(&str, &str); 5] = [
+    ("3c3da6925a3c3c268448dcabb47ccde5439559d9599646a8260e47b1e4822fc6", "09a6c8561a0b22bef63124c588ce4c62ea83a3c899763af26d795302e115dc21"),
+    ("608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad", "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531"),
+    ("6d7fabf47a2dc03fe7d47f7dddd21082c5fb8f86743cd020f3fb147d57161472", "53060a3d140e7fbcda641ed3cf42c88a75411e648a1add71217f70ea8ec561a6"),
+    ("5fb0b92acedd16f3bcb0ef83f5c7b7a9466b5f1e0d8d217421878ea3686f8524", "2eca15e355fcfa39d2982f67ddb0eea138e2994f5956ed37b7f72eea5e89d2f7"),
+    ("0efcfde5898a839b00997fbe40d2ebe950bc81181afbd5cd6b9618aa336c1e8c", "6dc2fc04f266c5c27f236a80b14f92ccd051ef1ff027f26a07f8c0f327d8f995"),
+];
+const H2C_EDWARDS25519_NU: [(&str, &str); 5] = [
+    ("1ff2b70ecf862799e11b7ae744e3489aa058ce805dd323a936375a84695e76da", "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b"),
+    ("5f13cc69c891d86927eb37bd4afc6672360007c63f68a33ab423a3aa040fd2a8", "67732d50f9a26f73111dd1ed5dba225614e538599db58ba30aaea1f5c827fa42"),
+    ("1dd2fefce934ecfd7aae6ec998de088d7dd03316aa1847198aecf699ba6613f1", "2f8a6c24dd1adde73909cada6a4a137577b0f179d336685c4a955a0a8e1a86fb"),
+    ("35fbdc5143e8a97afd3096f2b843e07df72e15bfca2eaf6879bf97c5d3362f73", "2af6ff6ef5ebba128b0774f4296cb4c2279a074658b083b8dcca91f57a603450"),
+    ("6e5e1f37e99345887fc12111575fc1c3e36df4b289b8759d23af14d774b66bff", "2c90c3d39eb18ff291d33441b35f3262cdd307162cc97c31bfcc7a4245891a37"),
+];
+
+#[test]
+fn expand_message_xmd_rfc9380() {
+    // Appendix K.3, SHA-512 with len_in_bytes = 0x20
+    let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+    assert_eq!(expand_message_xmd(b"", dst, 0x20).unwrap(), hex_bytes("6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba"));
+    assert_eq!(expand_message_xmd(b"abc", dst, 0x20).unwrap(), hex_bytes("0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc"));
+    // The length is bound into the output, so a longer expansion does not extend a shorter one
+    let long = expand_message_xmd(b"abc", dst, 0x85).unwrap();
+    assert_eq!(long.len(), 0x85);
+    assert_ne!(long[..0x20], expand_message_xmd(b"abc", dst, 0x20).unwrap()[..]);
+    // The largest expansions run the one-byte block counter up to 255
+    let longest = expand_message_xmd(b"abc", dst, 255 * 64).unwrap();
+    assert_eq!(longest.len(), 255 * 64);
+    assert_eq!(longest[255 * 64 - 32..], hex_bytes("3c0ef3ed762c2d948ebb2b9a6d4e6c518b93eccdfbd3c9a4a789bdd9271bf96a")[..]);
+    let longest = expand_message_xmd(b"abc", dst, 254 * 64 + 1).unwrap();
+    assert_eq!(longest.len(), 254 * 64 + 1);
+    assert_eq!(longest[254 * 64 - 32..], hex_bytes("c9436bbe49a7608cbe1cb76c462b41e287fbd8a813492c063d57d34633cc5f229b")[..]);
+    // Section 5.3.3: tags over 255 bytes are replaced by their hash
+    let long_dst = [b'd'; 256];
+    let hashed_dst = Sha512::new().chain_update(b"H2C-OVERSIZE-DST-").chain_update(&long_dst[..]).finalize();
+    assert_eq!(expand_message_xmd(b"abc", &long_dst, 0x20).unwrap(), expand_message_xmd(b"abc", &hashed_dst, 0x20).unwrap());
+}
+
+#[test]
+fn hash_to_curve_rejects() {
+    let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+    assert_eq!(expand_message_xmd(b"abc", dst, 255 * 64 + 1), Err(HashToCurveError::TooLong(255 * 64 + 1)));
+    assert_eq!(hash_to_field(b"abc", dst, 341), Err(HashToCurveError::TooLong(341 * 48)));
+    assert_eq!(expand_message_xmd(b"abc", b"", 0x20), Err(HashToCurveError::EmptyDst));
+    assert_eq!(hash_to_field(b"abc", b"", 1), Err(HashToCurveError::EmptyDst));
+    assert_eq!(hash_to_curve25519(b"abc", b""), Err(HashToCurveError::EmptyDst));
+    assert_eq!(encode_to_curve25519(b"abc", b""), Err(HashToCurveError::EmptyDst));
+    assert_eq!(hash_to_edwards25519(b"abc", b""), Err(HashToCurveError::EmptyDst));
+    assert_eq!(encode_to_edwards25519(b"abc", b""), Err(HashToCurveError::EmptyDst));
+}
+
+#[test]
+fn hash_to_curve_rfc9380() {
+    let dst = |suite: &str| format!("{}{}", H2C_DST_PREFIX, suite).into_bytes();
+    let edwards = |(x, y): &(&str, &str)| {
+        let (x, y) = (fe_from_be_hex(x), fe_from_be_hex(y));
+        EdwardsPoint { x, y, z: Fe25519::ONE, t: x * y }
+    };
+    let montgomery = |(u, v): &(&str, &str)| (fe_from_be_hex(u), fe_from_be_hex(v));
+    for (index, msg) in h2c_messages().iter().enumerate() {
+        assert_eq!(hash_to_field(msg, &dst("edwards25519_XMD:SHA-512_ELL2_NU_"), 1).unwrap(), vec![fe_from_be_hex(H2C_FIELD_NU[index])]);
+        let (u0, u1) = H2C_FIELD_RO[index];
+        assert_eq!(hash_to_field(msg, &dst("edwards25519_XMD:SHA-512_ELL2_RO_"), 2).unwrap(), vec![fe_from_be_hex(u0), fe_from_be_hex(u1)]);
+        assert_eq!(hash_to_curve25519(msg, &dst("curve25519_XMD:SHA-512_ELL2_RO_")).unwrap(), montgomery(&H2C_CURVE25519_RO[index]));
+        assert_eq!(encode_to_curve25519(msg, &dst("curve25519_XMD:SHA-512_ELL2_NU_")).unwrap(), montgomery(&H2C_CURVE25519_NU[index]));
+        assert_eq!(hash_to_edwards25519(msg, &dst("edwards25519_XMD:SHA-512_ELL2_RO_")).unwrap(), edwards(&H2C_EDWARDS25519_RO[index]));
+        assert_eq!(encode_to_edwards25519(msg, &dst("edwards25519_XMD:SHA-512_ELL2_NU_")).unwrap(), edwards(&H2C_EDWARDS25519_NU[index]));
+    }
+}
+
+// Base mode with DHKEM(X25519, HKDF-SHA256) and HKDF-SHA256, as (aead, skRm, skEm, pkRm, pkEm,
+// ciphertexts for sequence numbers 0, 1 and 256, exported values for the contexts "", "00" and
+// "TestContext"). The AES-128-GCM and ChaCha20-Poly1305 entries are RFC 9180 appendices A.1.1 and
+// A.2.1; the AES-256-GCM entry is not in the appendix but comes from the same CFRG
+// test-vectors.json, as BoringSSL ships it in crypto/hpke/hpke_test_vectors.txt
+type HpkeBaseVector = (HpkeAead, &'static str, &'static str, &'static str, &'static str, [&'static str; 3], [&'static str; 3]);
+const HPKE_BASE_VECTORS: [HpkeBaseVector; 3] = [
+    (
+        HpkeAead::Aes128Gcm,
+        "4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8",
+        "52c4a758a802cd8b936eceea314432798d5baf2d7e9235dc084ab1b9cfa2f736",
+        "3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d",
+        "37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431",
+        [
+            "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a96d8770ac83d07bea87e13c512a",
+            "af2d7e9ac9ae7e270f46ba1f975be53c09f8d875bdc8535458c2494e8a6eab251c03d0c22a56b8ca42c2063b84",
+            "957f9800542b0b8891badb026d79cc54597cb2d225b54c00c5238c25d05c30e3fbeda97d2e0e1aba483a2df9f2",
+        ],
+        [
+            "3853fe2b4035195a573ffc53856e77058e15d9ea064de3e59f4961d0095250ee",
+            "2e8f0b54673c7029649d4eb9d5e33bf1872cf76d623ff164ac185da9e88c21a5",
+            "e9e43065102c3836401bed8c3c3c75ae46be1639869391d62c61f1ec7af54931",
+        ],
+    ),
+    (
+        HpkeAead::Aes256Gcm,
+        "497b4502664cfea5d5af0b39934dac72242a74f8480451e1aee7d6a53320333d",
+        "179d4b53b6365c45b600c4163b61d95cbc2f4d9e36f1695558dce265ab8bab11",
+        "430f4b9859665145a6b1ba274024487bd66f03a2dd577d7753c68d7d7d00c00c",
+        "6c93e09869df3402d7bf231bf540fadd35cd56be14f97178f0954db94b7fc256",
+        [
+            "e5d84cd531cfb583096e7cfa9641bd3079cf3a91cda813c52deb5f512be9931980a41de125a925cdad859d5b7a",
+            "2c43aff25343fdbff864506f0818b9d87df84ea01b1a2144d23b4d40c26bf655fdf197fe40297a8aebeed5cc2d",
+            "53624f4f9f173453b14e633b45390ff54cacaa4428d44baee1bff8133fab1ab3afe60f88e4634b525c54e92eda",
+        ],
+        [
+            "ded6cffafaea6b812cbf3e241e88332adbc077aca81512914213810ee291770a",
+            "04d3cb6cc116b28ffd22ad5bc276c60d31fec71ceb87ae24db811c64b7507339",
+            "7c5ded445732c14fe09727d29b4251c0fd38455fe8440571e687f0886aac94d2",
+        ],
+    ),
+    (
+        HpkeAead::ChaCha20Poly1305,
+        "8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb",
+        "f4ec9b33b792c372c1d2c2063507b684ef925b8c75a42dbcbf57d63ccd381600",
+        "4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a",
+        "1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a",
+        [
+            "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28",
+            "6b53c051e4199c518de79594e1c4ab18b96f081549d45ce015be002090bb119e85285337cc95ba5f59992dc98c",
+            "7a4a13e9ef23978e2c520fd4d2e757514ae160cd0cd05e556ef692370ca53076214c0c40d4c728d6ed9e727a5b",
+        ],
+        [
+            "4bbd6243b8bb54cec311fac9df81841b6fd61f56538a775e7c80a9f40160606e",
+            "8c1df14732580e5501b00f82b10a1647b40713191b7c1240ac80e2b68808ba69",
+            "5acb09211139c43b3090489a9da433e8a30ee7188ba8b0a9a1ccf0c229283e53",
+        ],
+    ),
+];
+
+// RFC 9180 appendix A.1.2, PSK mode with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and AES-128-GCM,
+// as OpenSSL's test/hpke_test.c carries it: ikmE, ikmR, pkEm, pkRm, the ciphertexts for sequence
+// numbers 0 to 2 and the exported values for "", "00" and "TestContext"
+const HPKE_PSK_VECTOR: (&str, &str, &str, &str, [&str; 3], [&str; 3]) = (
+    "78628c354e46f3e169bd231be7b2ff1c77aa302460a26dbfa15515684c00130b",
+    "d4a09d09f575fef425905d2ab396c1449141463f698f8efdb7accfaff8995098",
+    "0ad0950d9fb9588e59690b74f1237ecdf1d775cd60be2eca57af5a4b0471c91b",
+    "9fed7e8c17387560e92cc6462a68049657246a09bfa8ade7aefe589672016366",
+    [
+        "e52c6fed7f758d0cf7145689f21bc1be6ec9ea097fef4e959440012f4feb73fb611b946199e681f4cfc34db8ea",
+        "49f3b19b28a9ea9f43e8c71204c00d4a490ee7f61387b6719db765e948123b45b61633ef059ba22cd62437c8ba",
+        "257ca6a08473dc851fde45afd598cc83e326ddd0abe1ef23baa3baa4dd8cde99fce2c1e8ce687b0b47ead1adc9",
+    ],
+    [
+        "dff17af354c8b41673567db6259fd6029967b4e1aad13023c2ae5df8f4f43bf6",
+        "6a847261d8207fe596befb52928463881ab493da345b10e1dcc645e3b94e2d95",
+        "8aff52b45a1be3a734bc7a41e20b4e055ad4c4d22104b0c20285a7c4302401cd",
+    ],
+);
+
+// Cross-checks of the PSK, Auth and AuthPsk modes against OpenSSL's independent implementation,
+// pending the RFC 9180 appendix vectors A.1.3, A.1.4 and A.2.2 to A.2.4: every mode over both
+// X25519 suites of the appendix, plus one HKDF-SHA384 and one HKDF-SHA512 suite. The IKMs are
+// chosen here; each entry is (mode, kdf, aead, ciphertexts for sequence numbers 0, 1 and 256,
+// exported values for "", "00" and "TestContext"). They come from OpenSSL 3.5.6's libcrypto
+// (any OpenSSL 3.2 or later has the same API): OSSL_HPKE_keygen on ikmR and ikmS, then a sender
+// context with OSSL_HPKE_CTX_set1_ikme(ikmE), OSSL_HPKE_CTX_set1_psk and OSSL_HPKE_CTX_set1_authpriv
+// as the mode needs, OSSL_HPKE_encap, OSSL_HPKE_seal with aad "Count-i" for i = 0 to 256 and three
+// OSSL_HPKE_export calls. The same steps reproduce appendix A.1.2 above
+const HPKE_IKM_R: &str = "00e55e3ae8de2cbbf03a20473460544f1a04afb7ff0e823a2458c436d5e4110f";
+const HPKE_IKM_S: &str = "1466b4828f478bffb9934886f34b08c368b5b5129cf7e9154289ccc4ca70b2dc";
+const HPKE_IKM_E: &str = "304213dc958598f2fca55cdb5db5f2315a41fb46ca85f3d5de016a56db0d603e";
+const HPKE_PSK: &str = "0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82";
+type HpkeModeVector = (Mode, HpkeKdf, HpkeAead, [&'static str; 3], [&'static str; 3]);
+const HPKE_MODE_VECTORS: [HpkeModeVector; 8] = [
+    (
+        Mode::Psk,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::Aes128Gcm,
+        [
+            "3ac5968257e0165dc11a2ffbeeadaeaceda28f67af6ce834b0d5aac8ca8993b5cc02ba7f9afba2fa0c97cea1fd",
+            "054e6eefb101b2a792d155d6694ac50fb509ae045394eb827d1d88bf0b8127a1b757e934fe03a31ac6270d9a2c",
+            "31c9f410093485a545377decb73690f49e5330137586d424558f80993b4c4ea4194f9ca2e21ce24da42aca355f",
+        ],
+        [
+            "efe21c0423d6ec1147ff07a152eda33d169d755750e1477834f323cfdcc99732",
+            "23d177fbac9e4bf419b54cfac38771e6edfd5a813e701f3b41f435b9610571c4",
+            "ed73d912c61853f317ca61bfd8d53582be7fbbffaec357109d6a58e08bcb08e3",
+        ],
+    ),
+    (
+        Mode::Auth,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::Aes128Gcm,
+        [
+            "76f4f6a8cf0daf68999e9515529a44c174ebf6b5647dd64e97d27d75af101230031f7031e945c606b18eb36563",
+            "c9dbbbf475283b6b6439ff47ec6941630f5554f79845c32ec9e1cf7c15de2386391858ae3644310a443888a160",
+            "f4c06122c7dcbd62552e22c99433b6ae074b34bd925bb3b97f069f205060dff7c7c58bf8fedaae283a747fe4d9",
+        ],
+        [
+            "cbdea9777c0874197d88517c46ea1511b5321c785806fa63f2e457ef1f80653d",
+            "aa5e6f0911cbdddc18d2b7427ad984c2d1b8c78632568bedb683a03660bd0c6f",
+            "db93d633a8acb18dd385a4faa7f5a65407eda98d1c6aacc19942489ee75863bd",
+        ],
+    ),
+    (
+        Mode::AuthPsk,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::Aes128Gcm,
+        [
+            "5596fd2d736047b046956ed821fddbc7d722d5849e388fbaf37dac9acb7d91effb6024cae5cad01c4f75ff6b8f",
+            "3f738e2922237d5fce9df24d8ed17c194af5af28972b868c9f59c5c16d95842bec24be126a14e2c6dda33f53aa",
+            "35a9c86ba76314fef99d6f6451a6e5a706f0bace91ee92b48323fa256f9651ca50ff7724c0a6ce413cd554280a",
+        ],
+        [
+            "0f5f8e936d437bdc4f295099707aae44a834a15f0f60c81daef07db1155ae7a8",
+            "5c6baf0f619e859e7e14f78e96287d554316d11ee90629ab6d5b4e56d9c6099d",
+            "6938e58bade2325abbe9d6ea49dda50c7141db28ebeb35a4bb754c26d1660d05",
+        ],
+    ),
+    (
+        Mode::Psk,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::ChaCha20Poly1305,
+        [
+            "0d68cba98089939235fce1de1e36bfb7cfe39c4571e16ff0378ded8950b66c887cc5aed69d926fb2f626baad20",
+            "6369e90efe85505ec19d75fa9696b388a5ec76c60d3f58345e71db070ff353d0702779044fe004ca1af33ee15e",
+            "951907525733040cea34eac41090b3281346e39afdfa2a684386c9478d1a36b04385734f6cd6f69a2454fecf54",
+        ],
+        [
+            "5600f11b8948825181d8e41f61b02c82bad9bd8b1fea092c8dd2a79966ed950d",
+            "bdb4b3d715f45ede2052460ee43f2dc2805df1d4e29cac77c01b4ad416683505",
+            "8fba3a9456bec38b74e042170eed395986b11f8c224b47f808b82e5db124c60e",
+        ],
+    ),
+    (
+        Mode::Auth,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::ChaCha20Poly1305,
+        [
+            "33168823c91f34e6516f266d3d90e4819b89c0099c95262b0a92415d048c455dfc10c32345c1ffdd43ffbc8b0e",
+            "1b7e50f14f3ee5660429a7ecfbb76576c6326a13163a2df7137a7e149ef1eb63b4c23a25528f14e65a7ec09a59",
+            "8f3f503a5908c6826c9b551121e5d2224fa06ccc4ce5e26cf665e652c526c69c6982dc13609c8fa7f80ee26fa0",
+        ],
+        [
+            "3e553986c4e69cce5cd19162a52084e001c70247049c3cbf82ed4a3e86a57ad4",
+            "0b0785178f31114c4f8b3a4895434abe924e11c29b0faad41a2deb72e887ff8c",
+            "ed2d7d267713d717ef750583c04fba3cca9c8cee4582cc6e22ca7530400aa353",
+        ],
+    ),
+    (
+        Mode::AuthPsk,
+        HpkeKdf::HkdfSha256,
+        HpkeAead::ChaCha20Poly1305,
+        [
+            "336edc8382c6a45c9d803c1bce3874c782af55077b92a397475149c27b1d345443c0e4131bad2f517d3ec518cf",
+            "adb9654103fc5e8fed738dcc39c3ea4b61f51af48fa8f0f47a53099ad7785fe593e6d84afe03e015dcc19eb536",
+            "57e5f210d231a84749f043c0ca5e7433f1d88a2d42db921073e34645c964e80167d019d50dcb4a4d9359fe5a61",
+        ],
+        [
+            "4fd2655de3b2a2870f135ff582405ede046272a1a1702830c3d8cb2ebe0f05fe",
+            "9fd4c938a9a173f0b9c8a7cddd3ce2632f7780de2ba606c90f1c291fe7526ffd",
+            "6a15acca976368873955007f4efe25779419a4d74d9f59acc33df63f680b4365",
+        ],
+    ),
+    (
+        Mode::Auth,
+        HpkeKdf::HkdfSha384,
+        HpkeAead::ChaCha20Poly1305,
+        [
+            "95758954d96125f57aa2adee73b71603e219bb7d1a55b0d05dd35e1e857879f98b2ae6a7f59f1df50db7016260",
+            "c0c84c303a134f088025491be71568738e4f0da01d4597132a1c566b8c4f984159da002a22d6ad9ffac5605275",
+            "cb04f91d6749950191cf193a6afe6ecbc91cfc684a2ed5b50d62090e6fd1ad076a6fcfd9c82d6dbbfa5f7dbc5d",
+        ],
+        [
+            "fa6408ad9accdc0a1cafb81265aa567023c3ebb2b913ca69748f335f14cafec6",
+            "d9f477d27f2968d9528bfcb12e26c0b9755311bc4132f40a0b3e4412e4fda0ae",
+            "3736df5b413606f094d0b86fedb1b2ec4c97176c3cb39f2b2a54f7ffb55f4ea4",
+        ],
+    ),
+    (
+        Mode::AuthPsk,
+        HpkeKdf::HkdfSha512,
+        HpkeAead::Aes256Gcm,
+        [
+            "7f0c6e8d5df359fdba63be93249299cd1bebecfae9f72e11c873161e8e02352562bae127b6b8843eb2cc041983",
+            "fa6f981d447d8b4190b64d37e2058cc009d0ae0aa843593d074f8e0422a23e59e204477e6e6ab706e2d1617966",
+            "d0b218a98337b1f045afb0e9310942802740d238d8a1746f6391c8dcecfa73dd5a6cb0f41fa2597e65895d4a21",
+        ],
+        [
+            "1eaea2eddb47447bd84dd848a3546526bffb66fe5c7056266f1918f6394f60fb",
+            "ee539d862ff78d2bfbc409975b783d2b9c7ac5bf47078c4687a5b966276235a8",
+            "4f5ffe731b092b3127c192c2e7ac09fa6476c709564da4f74a980076ddf3573a",
+        ],
+    ),
+];
+const HPKE_INFO: &[u8] = b"Ode on a Grecian Urn";
+const HPKE_PLAINTEXT: &[u8] = b"Beauty is truth, truth beauty";
+
+#[test]
+fn hpke_rfc9180() {
+    let exporter_contexts: [&[u8]; 3] = [b"", &[0x00], b"TestContext"];
+    for (aead, sk_rm, sk_em, pk_rm, pk_em, cts, exports) in HPKE_BASE_VECTORS.iter() {
+        let suite = HpkeSuite::new(HpkeKdf::HkdfSha256, *aead);
+        let sk_r = StaticSecret::from(hex_32(sk_rm));
+        assert_eq!(PublicKey::from(&sk_r).to_bytes(), hex_32(pk_rm));
+        let (enc, mut sender) =
+            setup_sender(suite, Mode::Base, &PublicKey::from(hex_32(pk_rm)), HPKE_INFO, (b"", b""), None, &StaticSecret::from(hex_32(sk_em)))
+                .unwrap();
+        assert_eq!(enc, hex_32(pk_em));
+        let mut receiver = setup_base_r(suite, &enc, &sk_r, HPKE_INFO).unwrap();
+        for seq in 0..257 {
+            let aad = format!("Count-{}", seq).into_bytes();
+            let ct = sender.seal(&aad, HPKE_PLAINTEXT).unwrap();
+            match seq {
+                0 => assert_eq!(ct, hex_bytes(cts[0])),
+                1 => assert_eq!(ct, hex_bytes(cts[1])),
+                256 => assert_eq!(ct, hex_bytes(cts[2])),
+                _ => (),
+            }
+            assert_eq!(receiver.open(&aad, &ct).unwrap(), HPKE_PLAINTEXT);
+        }
+        for (context, expected) in exporter_contexts.iter().zip(exports.iter()) {
+            assert_eq!(sender.export(context, 32).unwrap(), hex_bytes(expected));
+            assert_eq!(receiver.export(context, 32).unwrap(), hex_bytes(expected));
+        }
+    }
+}
+
+#[test]
+fn hpke_rfc9180_psk() {
+    let (ikm_e, ikm_r, pk_em, pk_rm, cts, exports) = HPKE_PSK_VECTOR;
+    let exporter_contexts: [&[u8]; 3] = [b"", &[0x00], b"TestContext"];
+    let suite = HpkeSuite::new(HpkeKdf::HkdfSha256, HpkeAead::Aes128Gcm);
+    let (sk_e, pk_e) = derive_key_pair(&hex_bytes(ikm_e));
+    let (sk_r, pk_r) = derive_key_pair(&hex_bytes(ikm_r));
+    assert_eq!(pk_e.to_bytes(), hex_32(pk_em));
+    assert_eq!(pk_r.to_bytes(), hex_32(pk_rm));
+    let (psk, psk_id) = (hex_bytes(HPKE_PSK), b"Ennyn Durin aran Moria");
+    let (enc, mut sender) = setup_sender(suite, Mode::Psk, &pk_r, HPKE_INFO, (&psk, psk_id), None, &sk_e).unwrap();
+    assert_eq!(enc, hex_32(pk_em));
+    let mut receiver = setup_psk_r(suite, &enc, &sk_r, HPKE_INFO, &psk, psk_id).unwrap();
+    for (seq, expected) in cts.iter().enumerate() {
+        let aad = format!("Count-{}", seq).into_bytes();
+        let ct = sender.seal(&aad, HPKE_PLAINTEXT).unwrap();
+        assert_eq!(ct, hex_bytes(expected));
+        assert_eq!(receiver.open(&aad, &ct).unwrap(), HPKE_PLAINTEXT);
+    }
+    for (context, expected) in exporter_contexts.iter().zip(exports.iter()) {
+        assert_eq!(sender.export(context, 32).unwrap(), hex_bytes(expected));
+        assert_eq!(receiver.export(context, 32).unwrap(), hex_bytes(expected));
+    }
+}
+
+#[test]
+fn hpke_modes() {
+    let (sk_r, pk_r) = derive_key_pair(&hex_bytes(HPKE_IKM_R));
+    let (sk_s, pk_s) = derive_key_pair(&hex_bytes(HPKE_IKM_S));
+    let (sk_e, pk_e) = derive_key_pair(&hex_bytes(HPKE_IKM_E));
+    assert_eq!(pk_r.to_bytes(), hex_32("ee30e5b29831b4846329b0f413236ef79056c93377e9743987a47fdadf7c0845"));
+    assert_eq!(pk_s.to_bytes(), hex_32("3a6ad20b8cf02755403633d602527d2d4323bd49a7cbf0935325b8f780d5f20d"));
+    assert_eq!(pk_e.to_bytes(), hex_32("ee42005bf1fe98862e3aeabfac8823ecc66819fee0b385c205ea4dbff994f531"));
+    let (psk, psk_id) = (hex_bytes(HPKE_PSK), b"Ennyn Durin aran Moria");
+    let exporter_contexts: [&[u8]; 3] = [b"", &[0x00], b"TestContext"];
+    for (mode, kdf, aead, cts, exports) in HPKE_MODE_VECTORS.iter() {
+        let suite = HpkeSuite::new(*kdf, *aead);
+        let (psk_pair, sender_key): ((&[u8], &[u8]), _) = match mode {
+            Mode::Psk => ((&psk, psk_id), None),
+            Mode::Auth => ((b"", b""), Some(&sk_s)),
+            _ => ((&psk, psk_id), Some(&sk_s)),
+        };
+        let (enc, mut sender) = setup_sender(suite, *mode, &pk_r, HPKE_INFO, psk_pair, sender_key, &sk_e).unwrap();
+        assert_eq!(enc, pk_e.to_bytes());
+        let mut receiver = match mode {
+            Mode::Psk => setup_psk_r(suite, &enc, &sk_r, HPKE_INFO, &psk, psk_id),
+            Mode::Auth => setup_auth_r(suite, &enc, &sk_r, HPKE_INFO, &pk_s),
+            _ => setup_auth_psk_r(suite, &enc, &sk_r, HPKE_INFO, &psk, psk_id, &pk_s),
+        }
+        .unwrap();
+        for seq in 0..257 {
+            let aad = format!("Count-{}", seq).into_bytes();
+            let ct = sender.seal(&aad, HPKE_PLAINTEXT).unwrap();
+            match seq {
+                0 => assert_eq!(ct, hex_bytes(cts[0])),
+                1 => assert_eq!(ct, hex_bytes(cts[1])),
+                256 => assert_eq!(ct, hex_bytes(cts[2])),
+                _ => (),
+            }
+            assert_eq!(receiver.open(&aad, &ct).unwrap(), HPKE_PLAINTEXT);
+        }
+        for (context, expected) in exporter_contexts.iter().zip(exports.iter()) {
+            assert_eq!(sender.export(context, 32).unwrap(), hex_bytes(expected));
+            assert_eq!(receiver.export(context, 32).unwrap(), hex_bytes(expected));
+        }
+    }
+}
+
+#[test]
+fn hpke_rejects() {
+    let mut rng = rand::thread_rng();
+    let suite = HpkeSuite::new(HpkeKdf::HkdfSha384, HpkeAead::ChaCha20Poly1305);
+    let sk_r = StaticSecret::random_from_rng(&mut rng);
+    let pk_r = PublicKey::from(&sk_r);
+    let sk_s = StaticSecret::random_from_rng(&mut rng);
+    let pk_s = PublicKey::from(&sk_s);
+    let other = PublicKey::from(&StaticSecret::random_from_rng(&mut rng));
+
+    // A tampered ciphertext fails without consuming a sequence number
+    let (enc, mut sender) = setup_base_s(suite, &pk_r, b"info", &mut rng).unwrap();
+    let mut receiver = setup_base_r(suite, &enc, &sk_r, b"info").unwrap();
+    let mut ct = sender.seal(b"aad", b"message").unwrap();
+    ct[0] ^= 1;
+    assert_eq!(receiver.open(b"aad", &ct), Err(HpkeError::OpenError));
+    ct[0] ^= 1;
+    assert_eq!(receiver.open(b"other aad", &ct), Err(HpkeError::OpenError));
+    assert_eq!(receiver.open(b"aad", &ct).unwrap(), b"message");
+    assert_eq!(receiver.open(b"aad", &ct), Err(HpkeError::OpenError));
+    assert_eq!(setup_base_r(suite, &enc, &sk_r, b"other info").unwrap().open(b"aad", &ct), Err(HpkeError::OpenError));
+    assert_eq!(sender.export(b"", 255 * 48).unwrap().len(), 255 * 48);
+    assert_eq!(sender.export(b"", 255 * 48 + 1), Err(HpkeError::ExportTooLong(255 * 48 + 1)));
+
+    // The PSK and the sender key must both match
+    let (enc, mut sender) = setup_auth_psk_s(suite, &pk_r, b"", b"0123456789abcdef0123456789abcdef", b"id", &sk_s, &mut rng).unwrap();
+    let ct = sender.seal(b"", b"message").unwrap();
+    let open = |receiver: Result<HpkeReceiverContext, HpkeError>| receiver.unwrap().open(b"", &ct);
+    assert_eq!(open(setup_auth_psk_r(suite, &enc, &sk_r, b"", b"0123456789abcdef0123456789abcdef", b"id", &pk_s)).unwrap(), b"message");
+    assert_eq!(open(setup_auth_psk_r(suite, &enc, &sk_r, b"", b"0123456789abcdef0123456789abcdeF", b"id", &pk_s)), Err(HpkeError::OpenError));
+    assert_eq!(open(setup_auth_psk_r(suite, &enc, &sk_r, b"", b"0123456789abcdef0123456789abcdef", b"ID", &pk_s)), Err(HpkeError::OpenError));
+    assert_eq!(open(setup_auth_psk_r(suite, &enc, &sk_r, b"", b"0123456789abcdef0123456789abcdef", b"id", &other)), Err(HpkeError::OpenError));
+    let (enc, mut sender) = setup_auth_s(suite, &pk_r, b"", &sk_s, &mut rng).unwrap();
+    let ct = sender.seal(b"", b"message").unwrap();
+    assert_eq!(setup_auth_r(suite, &enc, &sk_r, b"", &pk_s).unwrap().open(b"", &ct).unwrap(), b"message");
+    assert_eq!(setup_auth_r(suite, &enc, &sk_r, b"", &other).unwrap().open(b"", &ct), Err(HpkeError::OpenError));
+
+    // Both halves of the PSK are required in the PSK modes
+    assert_eq!(setup_psk_s(suite, &pk_r, b"", b"", b"id", &mut rng).err(), Some(HpkeError::InconsistentPsk));
+    assert_eq!(setup_psk_s(suite, &pk_r, b"", b"psk", b"", &mut rng).err(), Some(HpkeError::InconsistentPsk));
+    assert_eq!(setup_psk_r(suite, &enc, &sk_r, b"", b"", b"").err(), Some(HpkeError::InconsistentPsk));
+
+    // Low-order public keys are refused on both sides
+    let zero = PublicKey::from([0u8; 32]);
+    assert_eq!(setup_base_s(suite, &zero, b"", &mut rng).err(), Some(HpkeError::KeyAgreement(KeyAgreementError::NonContributory)));
+    assert_eq!(setup_base_r(suite, &[0u8; 32], &sk_r, b"").err(), Some(HpkeError::KeyAgreement(KeyAgreementError::NonContributory)));
+    assert_eq!(setup_auth_r(suite, &enc, &sk_r, b"", &zero).err(), Some(HpkeError::KeyAgreement(KeyAgreementError::NonContributory)));
+}
+
+// libsodium's box test (test/default/box.c): Alice's secret key, Bob's secret key, the nonce and
+// the 131-byte message, with the outputs of libsodium 1.0.18 for them
+const BOX_ALICE_SK: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+const BOX_BOB_SK: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+const BOX_NONCE: &str = "69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37";
+const BOX_MESSAGE: &str = "be075fc53c81f2d5cf141316ebeb0c7b5228c52a4c62cbd44b66849b64244ffce5ecbaaf33bd751a1ac728d45e6c61296cdc3c01233561f41db66cce314adb310e3be8250c46f06dceea3a7fa1348057e2f6556ad6b1318a024a838f21af1fde048977eb48f59ffd4924ca1c60902e52f0a089bc76897040e082f937763848645e0705";
+const BOX_CIPHERTEXT: &str = "f3ffc7703f9400e52a7dfb4b3d3305d98e993b9f48681273c29650ba32fc76ce48332ea7164d96a4476fb8c531a1186ac0dfc17c98dce87b4da7f011ec48c97271d2c20f9b928fe2270d6fb863d51738b48eeee314a7cc8ab932164548e526ae90224368517acfeabd6bb3732bc0e9da99832b61ca01b6de56244a9e88d5f9b37973f622a43d14a6599b1f654cb45a74e355a5";
+
+#[test]
+fn crypto_box_libsodium() {
+    let alice_sk = StaticSecret::from(hex_32(BOX_ALICE_SK));
+    let bob_sk = StaticSecret::from(hex_32(BOX_BOB_SK));
+    let alice_pk = PublicKey::from(&alice_sk);
+    let bob_pk = PublicKey::from(&bob_sk);
+    assert_eq!(alice_pk.to_bytes(), hex_32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(bob_pk.to_bytes(), hex_32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+    let key = crypto_box_beforenm(&bob_pk, &alice_sk).unwrap();
+    assert_eq!(key, hex_32("1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389"));
+    assert_eq!(crypto_box_beforenm(&alice_pk, &bob_sk).unwrap(), key);
+
+    let mut nonce = [0u8; 24];
+    nonce.copy_from_slice(&hex_bytes(BOX_NONCE));
+    let message = hex_bytes(BOX_MESSAGE);
+    let ciphertext = crypto_box_easy(&message, &nonce, &bob_pk, &alice_sk).unwrap();
+    assert_eq!(ciphertext, hex_bytes(BOX_CIPHERTEXT));
+    assert_eq!(crypto_box_easy_afternm(&message, &nonce, &key), ciphertext);
+    assert_eq!(crypto_box_open_easy(&ciphertext, &nonce, &alice_pk, &bob_sk).unwrap(), message);
+    assert_eq!(crypto_box_open_easy_afternm(&ciphertext, &nonce, &key).unwrap(), message);
+    let empty = crypto_box_easy(b"", &nonce, &bob_pk, &alice_sk).unwrap();
+    assert_eq!(empty, hex_bytes("2539121d8e234e652d651fa4c8cff880"));
+    assert_eq!(crypto_box_open_easy(&empty, &nonce, &alice_pk, &bob_sk).unwrap(), b"");
+
+    // crypto_box_seal with libsodium's ephemeral key pair pinned to 00 01 .. 1f, then to a5 .. a5
+    let esk = StaticSecret::from(hex_32("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"));
+    let sealed = hex_bytes(
+        "8f40c5adb68f25624ae5b214ea767a6ec94d829d3d7b5e1ad1ba6f3e2138285f8081067b3657bf476a5679d0ed41c43486315ff29bfff30d8bc3629c0bdd",
+    );
+    assert_eq!(crypto_box_seal_with(b"Sealed for Bob", &bob_pk, &esk).unwrap(), sealed);
+    assert_eq!(crypto_box_seal_open(&sealed, &bob_pk, &bob_sk).unwrap(), b"Sealed for Bob");
+    let esk = StaticSecret::from([0xa5; 32]);
+    let sealed = hex_bytes("5fef13fc76023a9ee6ded987b6aa93958cdc2097ef9fc845d5319c9ca100d35e161c8238dcec3dc5a1239a2d7d01c0c9");
+    assert_eq!(crypto_box_seal_with(b"", &bob_pk, &esk).unwrap(), sealed);
+    assert_eq!(crypto_box_seal_open(&sealed, &bob_pk, &bob_sk).unwrap(), b"");
+}
+
+#[test]
+fn crypto_box_rejects() {
+    let mut rng = rand::thread_rng();
+    let (alice_pk, alice_sk) = crypto_box_keypair(&mut rng);
+    let (bob_pk, bob_sk) = crypto_box_keypair(&mut rng);
+    assert_eq!(PublicKey::from(&alice_sk), alice_pk);
+    let mut nonce = [0u8; CRYPTO_BOX_NONCEBYTES];
+    rng.fill(&mut nonce);
+    for length in [0, 1, 63, 64, 65, 1000].iter() {
+        let mut message = vec![0u8; *length];
+        rng.fill(&mut message[..]);
+        let mut ciphertext = crypto_box_easy(&message, &nonce, &bob_pk, &alice_sk).unwrap();
+        assert_eq!(ciphertext.len(), length + CRYPTO_BOX_MACBYTES);
+        assert_eq!(crypto_box_open_easy(&ciphertext, &nonce, &alice_pk, &bob_sk).unwrap(), message);
+        let mut sealed = crypto_box_seal(&message, &bob_pk, &mut rng).unwrap();
+        assert_eq!(sealed.len(), length + CRYPTO_BOX_SEALBYTES);
+        assert_eq!(crypto_box_seal_open(&sealed, &bob_pk, &bob_sk).unwrap(), message);
+        // Any flipped bit, in the tag, the body or the ephemeral key, is caught
+        let index = rng.gen_range(0, ciphertext.len());
+        ciphertext[index] ^= 1 << rng.gen_range(0, 8);
+        assert_eq!(crypto_box_open_easy(&ciphertext, &nonce, &alice_pk, &bob_sk), Err(CryptoBoxError::OpenError));
+        let index = rng.gen_range(0, sealed.len());
+        sealed[index] ^= 1 << rng.gen_range(0, 8);
+        assert!(crypto_box_seal_open(&sealed, &bob_pk, &bob_sk).is_err());
+    }
+
+    // The wrong keys or nonce fail to open
+    let ciphertext = crypto_box_easy(b"message", &nonce, &bob_pk, &alice_sk).unwrap();
+    assert_eq!(crypto_box_open_easy(&ciphertext, &nonce, &bob_pk, &bob_sk), Err(CryptoBoxError::OpenError));
+    let mut other_nonce = nonce;
+    other_nonce[23] ^= 1;
+    assert_eq!(crypto_box_open_easy(&ciphertext, &other_nonce, &alice_pk, &bob_sk), Err(CryptoBoxError::OpenError));
+    let sealed = crypto_box_seal(b"message", &bob_pk, &mut rng).unwrap();
+    assert_eq!(crypto_box_seal_open(&sealed, &alice_pk, &alice_sk), Err(CryptoBoxError::OpenError));
+
+    // Truncated inputs and low-order public keys
+    assert_eq!(crypto_box_open_easy(&ciphertext[..15], &nonce, &alice_pk, &bob_sk), Err(CryptoBoxError::TooShort(15)));
+    assert_eq!(crypto_box_seal_open(&sealed[..47], &bob_pk, &bob_sk), Err(CryptoBoxError::TooShort(47)));
+    let zero = PublicKey::from([0u8; 32]);
+    let non_contributory = Some(CryptoBoxError::KeyAgreement(KeyAgreementError::NonContributory));
+    assert_eq!(crypto_box_beforenm(&zero, &alice_sk).err(), non_contributory);
+    assert_eq!(crypto_box_easy(b"message", &nonce, &zero, &alice_sk).err(), non_contributory);
+    assert_eq!(crypto_box_open_easy(&ciphertext, &nonce, &zero, &bob_sk).err(), non_contributory);
+    assert_eq!(crypto_box_seal(b"message", &zero, &mut rng).err(), non_contributory);
+}